@@ -0,0 +1,129 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ffrs::{BkTree, FuzzyOptions, QGramIndex, Searcher, SymSpellIndex};
+
+const WORDS: usize = 500_000;
+
+// a fixed pseudo-random dictionary, so that runs are comparable
+fn dictionary() -> Vec<String> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    (0..WORDS)
+        .map(|_| {
+            let length = 4 + next() % 7;
+            (0..length)
+                .map(|_| (b'a' + (next() % 26) as u8) as char)
+                .collect()
+        })
+        .collect()
+}
+
+// words of the dictionary with one or two typos
+fn queries(words: &[String]) -> Vec<String> {
+    words
+        .iter()
+        .step_by(WORDS / 20)
+        .enumerate()
+        .map(|(i, word)| {
+            let mut chars = word.chars().collect::<Vec<_>>();
+            chars.swap(1, 2);
+            if i % 2 == 0 {
+                chars.remove(0);
+            }
+            chars.into_iter().collect()
+        })
+        .collect()
+}
+
+fn options() -> FuzzyOptions<String> {
+    FuzzyOptions::default().use_sellers(false).threshold(0.7)
+}
+
+fn bench_search(c: &mut Criterion) {
+    let words = dictionary();
+    let queries = queries(&words);
+
+    let trie = Searcher::new(words.clone(), options());
+    let symspell = Searcher::with_index(words.clone(), options(), SymSpellIndex::new());
+    let bktree = Searcher::with_index(words.clone(), options(), BkTree::new());
+    let qgram = Searcher::with_index(words.clone(), options(), QGramIndex::new());
+
+    let mut group = c.benchmark_group("search 500k words");
+    group.sample_size(10);
+    group.bench_function("trie", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(trie.search(query).unwrap());
+            }
+        })
+    });
+    group.bench_function("symspell", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(symspell.search(query).unwrap());
+            }
+        })
+    });
+    group.bench_function("bktree", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(bktree.search(query).unwrap());
+            }
+        })
+    });
+    group.bench_function("qgram", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(qgram.search(query).unwrap());
+            }
+        })
+    });
+    group.bench_function("trie, search_many", |b| {
+        b.iter(|| black_box(trie.search_many(&queries).unwrap()))
+    });
+    group.finish();
+}
+
+fn bench_build(c: &mut Criterion) {
+    let words = dictionary();
+
+    let mut group = c.benchmark_group("build 500k words");
+    group.sample_size(10);
+    group.bench_function("trie", |b| {
+        b.iter_batched(
+            || words.clone(),
+            |words| Searcher::new(words, options()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("symspell", |b| {
+        b.iter_batched(
+            || words.clone(),
+            |words| Searcher::with_index(words, options(), SymSpellIndex::new()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("bktree", |b| {
+        b.iter_batched(
+            || words.clone(),
+            |words| Searcher::with_index(words, options(), BkTree::new()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("qgram", |b| {
+        b.iter_batched(
+            || words.clone(),
+            |words| Searcher::with_index(words, options(), QGramIndex::new()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_search, bench_build);
+criterion_main!(benches);
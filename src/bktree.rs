@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use crate::{
+    finish_matches,
+    score::max_edits,
+    score_candidate,
+    trie::{keep_best, Candidate, TrieMatch},
+    util::{normalize, normalize_key},
+    FuzzyOptions,
+};
+
+struct Node {
+    key: Vec<String>,
+    // every form of a key normalized to `key`; removing them all leaves the
+    // node in place to route the search to its children
+    candidates: Vec<Candidate>,
+    // child nodes by their distance from this one
+    children: HashMap<usize, usize>,
+}
+
+/// A BK-tree of the normalized keys, for finding every key within some
+/// distance of the term without measuring most of them.
+///
+/// The tree is built on unrestricted Damerau-Levenshtein distance, which
+/// unlike the restricted distance the scorer uses obeys the triangle
+/// inequality.
+/// The radius searched is wide enough to hold every key that can reach the
+/// threshold under the configured costs, and the keys found are then scored
+/// as usual. It pays off when a high threshold keeps the distance searched
+/// small. It is meant for `use_sellers(false)`, and scores every key for
+/// Sellers matches, structured match kinds and numeric tokens.
+#[derive(Default)]
+pub struct BkTree {
+    nodes: Vec<Node>,
+}
+
+// Unrestricted Damerau-Levenshtein distance over graphemes, which unlike the
+// restricted distance the scorer uses is a metric. The scorer's distance is
+// never lower, and counts every edit this one does.
+fn distance(a: &[String], b: &[String]) -> usize {
+    let width = b.len() + 2;
+    let max = a.len() + b.len();
+    let mut rows = vec![0; (a.len() + 2) * width];
+    // the last row each grapheme of `a` was seen in, few enough to search
+    let mut last_rows: Vec<(&str, usize)> = vec![];
+
+    rows[0] = max;
+    for i in 0..=a.len() {
+        rows[(i + 1) * width] = max;
+        rows[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=b.len() {
+        rows[j + 1] = max;
+        rows[width + j + 1] = j;
+    }
+
+    for i in 1..=a.len() {
+        // the last column in this row where the graphemes matched
+        let mut last_column = 0;
+        for j in 1..=b.len() {
+            let k = last_rows
+                .iter()
+                .find(|(grapheme, _)| *grapheme == b[j - 1])
+                .map_or(0, |&(_, row)| row);
+            let l = last_column;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_column = j;
+                0
+            } else {
+                1
+            };
+
+            rows[(i + 1) * width + j + 1] = (rows[i * width + j] + cost)
+                .min(rows[(i + 1) * width + j] + 1)
+                .min(rows[i * width + j + 1] + 1)
+                .min(rows[k * width + l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        match last_rows
+            .iter_mut()
+            .find(|(grapheme, _)| *grapheme == a[i - 1])
+        {
+            Some((_, row)) => *row = i,
+            None => last_rows.push((&a[i - 1], i)),
+        }
+    }
+
+    rows[(a.len() + 1) * width + b.len() + 1]
+}
+
+impl BkTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn add<T, U>(&mut self, index: usize, item: &T, options: &FuzzyOptions<T, U>) {
+        let keys = (options.key_selector)(item);
+
+        for (key_index, key) in keys.into_iter().enumerate() {
+            for normalized in normalize_key(key, options) {
+                self.insert(Candidate {
+                    index,
+                    key_index,
+                    normalized,
+                });
+            }
+        }
+    }
+
+    fn insert(&mut self, candidate: Candidate) {
+        let key = &candidate.normalized.normal;
+        let mut current = 0;
+
+        while current < self.nodes.len() {
+            let node = &mut self.nodes[current];
+            let distance = distance(key, &node.key);
+            if distance == 0 {
+                node.candidates.push(candidate);
+                return;
+            }
+
+            match node.children.get(&distance) {
+                Some(&child) => current = child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes[current].children.insert(distance, child);
+                    break;
+                }
+            }
+        }
+
+        self.nodes.push(Node {
+            key: key.clone(),
+            candidates: vec![candidate],
+            children: HashMap::new(),
+        });
+    }
+
+    pub(crate) fn remove<T, U>(&mut self, index: usize, item: &T, options: &FuzzyOptions<T, U>) {
+        let keys = (options.key_selector)(item);
+
+        for key in keys {
+            for normalized in normalize_key(key, options) {
+                let mut current = (!self.nodes.is_empty()).then_some(0);
+                while let Some(node) = current.map(|current| &mut self.nodes[current]) {
+                    let distance = distance(&normalized.normal, &node.key);
+                    if distance == 0 {
+                        node.candidates.retain(|candidate| candidate.index != index);
+                        break;
+                    }
+                    current = node.children.get(&distance).copied();
+                }
+            }
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    pub(crate) fn candidates(&self) -> impl Iterator<Item = &Candidate> {
+        self.nodes.iter().flat_map(|node| &node.candidates)
+    }
+
+    // the keys within `radius` of `term`
+    fn within(&self, term: &[String], radius: usize) -> Vec<&Candidate> {
+        let mut found = vec![];
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(current) = stack.pop() {
+            let node = &self.nodes[current];
+            let distance = distance(term, &node.key);
+            if distance <= radius {
+                found.extend(&node.candidates);
+            }
+
+            // by the triangle inequality, only children this far from the
+            // node can be within the radius of the term
+            let range = distance.saturating_sub(radius)..=distance + radius;
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(distance, _)| range.contains(distance))
+                    .map(|(_, &child)| child),
+            );
+        }
+
+        found
+    }
+
+    pub(crate) fn search<T, U>(&self, term: &str, options: &FuzzyOptions<T, U>) -> Vec<TrieMatch> {
+        let normal_term = normalize(term.to_string(), options).normal;
+        // a transposition is a single edit in this distance too
+        let radius = if options.use_sellers {
+            None
+        } else {
+            max_edits(normal_term.len(), options, 1.0)
+        };
+        let candidates = match radius {
+            Some(radius) => self.within(&normal_term, radius),
+            None => self.candidates().collect(),
+        };
+
+        let mut results = HashMap::new();
+        for candidate in candidates {
+            let result = score_candidate(term, &normal_term, candidate, options);
+            if result.score >= options.threshold {
+                keep_best(&mut results, result);
+            }
+        }
+
+        finish_matches(results, options)
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::{
+    finish_matches, fuzzy_match,
+    trie::{keep_best, TrieMatch},
+    util::normalize,
+    FuzzyOptions, Index,
+};
+
+/// An index that scores every key of every item, as [`fuzzy_data`] would.
+///
+/// It is as slow as a search can be, but shares none of the pruning of the
+/// other backends, which makes it the reference they are tested against.
+///
+/// [`fuzzy_data`]: crate::fuzzy_data
+#[derive(Default)]
+pub struct BruteForceIndex;
+
+impl BruteForceIndex {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<T> Index<T> for BruteForceIndex {
+    // queries score the items themselves, so there is nothing to keep
+    fn add(&mut self, _index: usize, _item: &T, _options: &FuzzyOptions<T>) {}
+
+    fn remove(&mut self, _index: usize, _item: &T, _options: &FuzzyOptions<T>) {}
+
+    fn clear(&mut self) {}
+
+    fn query(&self, term: &str, items: &[Option<T>], options: &FuzzyOptions<T>) -> Vec<TrieMatch> {
+        let normal_term = normalize(term.to_string(), options).normal;
+        let mut results = HashMap::new();
+
+        for (index, item) in items.iter().enumerate() {
+            let Some(item) = item else {
+                continue;
+            };
+
+            for (key_index, key) in (options.key_selector)(item).into_iter().enumerate() {
+                let result = fuzzy_match(term, &normal_term, index, key_index, key, options);
+                if result.score >= options.threshold {
+                    keep_best(&mut results, result);
+                }
+            }
+        }
+
+        finish_matches(results, options)
+    }
+}
@@ -0,0 +1,72 @@
+use caseless::default_case_fold_str;
+use unicode_normalization::char::{canonical_combining_class, is_combining_mark};
+
+use crate::{CaseMode, Locale};
+
+const COMBINING_DOT_ABOVE: char = '\u{307}';
+// the canonical combining class of accents placed above the base letter
+const ABOVE: u8 = 230;
+
+/// Changes the case of the grapheme found at `index` of `string`, which is
+/// needed for the context some rules depend on.
+///
+/// The result may be longer than the grapheme, as full case folding expands
+/// `ß` into `ss` among others.
+pub(crate) fn change_case(string: &str, index: usize, grapheme: &str, mode: CaseMode) -> String {
+    match mode {
+        CaseMode::Lower if is_final_sigma(string, index, grapheme) => {
+            format!("ς{}", grapheme['Σ'.len_utf8()..].to_lowercase())
+        }
+        CaseMode::Lower => grapheme.to_lowercase(),
+        CaseMode::Fold => default_case_fold_str(grapheme),
+        CaseMode::FoldLocale(locale) => default_case_fold_str(&tailor(grapheme, locale)),
+    }
+}
+
+fn is_final_sigma(string: &str, index: usize, grapheme: &str) -> bool {
+    let before = string[..index]
+        .chars()
+        .rev()
+        .find(|char| !is_combining_mark(*char));
+    let after = string[index + grapheme.len()..].chars().next();
+
+    grapheme.starts_with('Σ')
+        && before.is_some_and(char::is_alphabetic)
+        && !after.is_some_and(char::is_alphabetic)
+}
+
+// Lowercases the letters whose casing differs in `locale`, leaving the rest of
+// the grapheme for default case folding.
+fn tailor(grapheme: &str, locale: Locale) -> String {
+    let mut chars = grapheme.chars();
+    let Some(first) = chars.next() else {
+        return String::new();
+    };
+    let rest = chars.as_str();
+
+    match locale {
+        Locale::Turkish | Locale::Azeri => match first {
+            'İ' => format!("i{rest}"),
+            'I' => match rest.strip_prefix(COMBINING_DOT_ABOVE) {
+                Some(rest) => format!("i{rest}"),
+                None => format!("ı{rest}"),
+            },
+            _ => grapheme.to_string(),
+        },
+        Locale::Lithuanian => {
+            let more_above = rest
+                .chars()
+                .any(|char| canonical_combining_class(char) == ABOVE);
+
+            match first {
+                'I' if more_above => format!("i{COMBINING_DOT_ABOVE}{rest}"),
+                'J' if more_above => format!("j{COMBINING_DOT_ABOVE}{rest}"),
+                'Į' if more_above => format!("į{COMBINING_DOT_ABOVE}{rest}"),
+                'Ì' => format!("i{COMBINING_DOT_ABOVE}\u{300}{rest}"),
+                'Í' => format!("i{COMBINING_DOT_ABOVE}\u{301}{rest}"),
+                'Ĩ' => format!("i{COMBINING_DOT_ABOVE}\u{303}{rest}"),
+                _ => grapheme.to_string(),
+            }
+        }
+    }
+}
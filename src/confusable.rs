@@ -0,0 +1,79 @@
+use std::fmt::Debug;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{fold::skeleton, search_data, FuzzyOptions};
+
+// how close the skeletons must be for a candidate to be reported: one edit in
+// a five letter name
+const CLOSE: f64 = 0.8;
+
+/// A candidate that looks like the term.
+#[derive(PartialEq, Debug)]
+pub struct ConfusableMatch<T>
+where
+    T: PartialEq + Debug,
+{
+    pub item: T,
+    /// 1 when the candidate's skeleton is the term's, less when they are only
+    /// close.
+    pub score: f64,
+    pub substitutions: Vec<Substitution>,
+}
+
+/// A character of a candidate that was read as another, because it looks the
+/// same.
+#[derive(PartialEq, Debug)]
+pub struct Substitution {
+    /// The byte index of the character in the candidate.
+    pub index: usize,
+    pub original: String,
+    pub prototype: String,
+}
+
+/// Finds the candidates that look the same as `term`, or almost, once every
+/// character is mapped to its Unicode TR39 prototype. This catches names such
+/// as `pаypal`, with a Cyrillic `а`, posing as `paypal`.
+///
+/// Each match lists the characters of the candidate that were substituted,
+/// leaving out those that also appear in the term. Matches are sorted best
+/// first.
+pub fn confusable_matches<T>(term: impl ToString, candidates: Vec<T>) -> Vec<ConfusableMatch<T>>
+where
+    T: ToString + PartialEq + Debug,
+{
+    let term = term.to_string();
+    let options = FuzzyOptions::default()
+        .fold_confusables(true)
+        .use_sellers(false)
+        .threshold(CLOSE);
+
+    let Ok(results) = search_data(&term, candidates, &options);
+    let term = term.to_lowercase();
+
+    results
+        .into_iter()
+        .map(|result| {
+            let key = result.item.to_string();
+            let substitutions = key
+                .grapheme_indices(true)
+                .filter_map(|(index, original)| {
+                    let lower = original.to_lowercase();
+                    let prototype = skeleton(&lower).to_lowercase();
+
+                    (prototype != lower && !term.contains(&lower)).then(|| Substitution {
+                        index,
+                        original: original.to_string(),
+                        prototype,
+                    })
+                })
+                .collect();
+
+            ConfusableMatch {
+                item: result.item,
+                score: result.score,
+                substitutions,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+/// Costs of the edit operations used when scoring a term against a candidate.
+///
+/// Operations are described as edits turning the term into the candidate, so
+/// `insert` is paid for a grapheme the candidate has but the term is missing,
+/// and `delete` for a stray grapheme in the term. Scores are still normalized
+/// by the term length, so the defaults (all `1.0`) give plain
+/// Levenshtein/Damerau scores.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditCosts {
+    pub(crate) insert: f64,
+    pub(crate) delete: f64,
+    pub(crate) substitute: f64,
+    pub(crate) transpose: f64,
+    pub(crate) substitutions: Option<SubstitutionTable>,
+}
+
+impl Default for EditCosts {
+    fn default() -> Self {
+        Self {
+            insert: 1.0,
+            delete: 1.0,
+            substitute: 1.0,
+            transpose: 1.0,
+            substitutions: None,
+        }
+    }
+}
+
+impl EditCosts {
+    pub fn insert(mut self, insert: f64) -> Self {
+        self.insert = check_cost(insert);
+        self
+    }
+
+    pub fn delete(mut self, delete: f64) -> Self {
+        self.delete = check_cost(delete);
+        self
+    }
+
+    pub fn substitute(mut self, substitute: f64) -> Self {
+        self.substitute = check_cost(substitute);
+        self
+    }
+
+    pub fn transpose(mut self, transpose: f64) -> Self {
+        self.transpose = check_cost(transpose);
+        self
+    }
+
+    pub fn substitutions(mut self, substitutions: SubstitutionTable) -> Self {
+        self.substitutions = Some(substitutions);
+        self
+    }
+
+    pub(crate) fn substitution(&self, a: &str, b: &str) -> f64 {
+        if a == b {
+            return 0.0;
+        }
+
+        self.substitutions
+            .as_ref()
+            .and_then(|table| table.get(a, b))
+            .unwrap_or(self.substitute)
+    }
+
+    /// The least an insertion, deletion or substitution can cost.
+    pub(crate) fn cheapest_edit(&self) -> f64 {
+        let substitute = self
+            .substitutions
+            .iter()
+            .flat_map(|table| table.costs.values())
+            .fold(self.substitute, |cheapest, &cost| cheapest.min(cost));
+
+        self.insert.min(self.delete).min(substitute)
+    }
+}
+
+fn check_cost(cost: f64) -> f64 {
+    assert!(
+        cost.is_finite() && cost >= 0.0,
+        "edit costs must be finite and non-negative, got {cost}"
+    );
+    cost
+}
+
+/// Per-pair substitution costs, overriding [`EditCosts`]'s `substitute` cost.
+///
+/// Pairs are symmetric and compared against normalized graphemes, so tables
+/// meant for case-insensitive search only need lowercase entries. Tables
+/// meant for case-sensitive search need the uppercase ones as well, which
+/// [`confusables`](Self::confusables) includes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubstitutionTable {
+    costs: HashMap<(String, String), f64>,
+}
+
+const QWERTY: [(&str, f64); 4] = [
+    ("1234567890-=", 0.0),
+    ("qwertyuiop[]", 0.5),
+    ("asdfghjkl;'", 0.75),
+    ("zxcvbnm,./", 1.25),
+];
+
+const AZERTY: [(&str, f64); 4] = [
+    ("1234567890)=", 0.0),
+    ("azertyuiop^$", 0.5),
+    ("qsdfghjklmù*", 0.75),
+    ("<wxcvbn,;:!", 0.25),
+];
+
+const DVORAK: [(&str, f64); 4] = [
+    ("1234567890[]", 0.0),
+    ("',.pyfgcrl/=", 0.5),
+    ("aoeuidhtns-", 0.75),
+    (";qjkxbmwvz", 1.25),
+];
+
+const CONFUSABLES: [(&str, &str); 16] = [
+    ("0", "o"),
+    ("0", "O"),
+    ("O", "o"),
+    ("1", "l"),
+    ("1", "i"),
+    ("1", "I"),
+    ("l", "i"),
+    ("l", "I"),
+    ("I", "i"),
+    ("5", "s"),
+    ("5", "S"),
+    ("2", "z"),
+    ("2", "Z"),
+    ("8", "b"),
+    ("8", "B"),
+    ("9", "g"),
+];
+
+impl SubstitutionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, a: impl Into<String>, b: impl Into<String>, cost: f64) {
+        let (a, b) = (a.into(), b.into());
+        let cost = check_cost(cost);
+        self.costs.insert((b.clone(), a.clone()), cost);
+        self.costs.insert((a, b), cost);
+    }
+
+    pub fn with(mut self, a: impl Into<String>, b: impl Into<String>, cost: f64) -> Self {
+        self.insert(a, b, cost);
+        self
+    }
+
+    pub fn get(&self, a: &str, b: &str) -> Option<f64> {
+        self.costs.get(&(a.to_string(), b.to_string())).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    /// Combines two tables, keeping the cheaper cost for pairs present in both.
+    pub fn merge(mut self, other: SubstitutionTable) -> Self {
+        for (pair, cost) in other.costs {
+            let entry = self.costs.entry(pair).or_insert(cost);
+            *entry = entry.min(cost);
+        }
+        self
+    }
+
+    pub fn qwerty(cost: f64) -> Self {
+        Self::keyboard(&QWERTY, cost)
+    }
+
+    pub fn azerty(cost: f64) -> Self {
+        Self::keyboard(&AZERTY, cost)
+    }
+
+    pub fn dvorak(cost: f64) -> Self {
+        Self::keyboard(&DVORAK, cost)
+    }
+
+    /// Visually confusable characters, such as `0`/`O` and `1`/`l`, in both
+    /// cases so that they also apply to a case-sensitive search.
+    pub fn confusables(cost: f64) -> Self {
+        let mut table = Self::new();
+        for (a, b) in CONFUSABLES {
+            table.insert(a, b, cost);
+        }
+        table
+    }
+
+    /// Builds an adjacency table from keyboard rows, given with the horizontal
+    /// offset of their first key. Keys on the same row are adjacent when they
+    /// are next to each other, keys on neighbouring rows when they overlap.
+    fn keyboard(rows: &[(&str, f64)], cost: f64) -> Self {
+        let keys = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, (keys, offset))| {
+                keys.chars()
+                    .enumerate()
+                    .map(move |(column, key)| (key, row, column as f64 + offset))
+            })
+            .collect::<Vec<_>>();
+
+        let mut table = Self::new();
+        for (i, &(a, row_a, x_a)) in keys.iter().enumerate() {
+            for &(b, row_b, x_b) in &keys[i + 1..] {
+                let adjacent = match row_a.abs_diff(row_b) {
+                    0 => (x_a - x_b).abs() <= 1.0,
+                    1 => (x_a - x_b).abs() < 1.0,
+                    _ => false,
+                };
+
+                if adjacent {
+                    table.insert(a.to_string(), b.to_string(), cost);
+                }
+            }
+        }
+        table
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt;
+
+use crate::{
+    numeric, phonetic,
+    score::Scorer,
+    score_candidate, structured_match,
+    trie::{compare_matches, Candidate},
+    util::{normalize, normalize_key},
+    BoostMode, Expansion, FuzzyOptions, MatchKind,
+};
+
+// leaves room for rounding when retracing fractional costs
+const EPSILON: f64 = 1e-9;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EditKind {
+    Match,
+    Substitute,
+    /// A grapheme of the key that the term is missing.
+    Insert,
+    /// A grapheme of the term that the key is missing.
+    Delete,
+    Transpose,
+}
+
+/// One step of the alignment of the term with a key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Edit {
+    pub kind: EditKind,
+    /// The graphemes of the term the step covers, empty for an insertion.
+    pub term: String,
+    /// The graphemes of the key the step covers, empty for a deletion.
+    pub key: String,
+    pub cost: f64,
+}
+
+/// How the score of a key was reached, step by step.
+///
+/// Scores are computed in the order of the fields: the edit distance over the
+/// length gives the text score, which a structured match can replace, which is
+/// then blended with the phonetic agreement, ruled out by differing numbers,
+/// lowered for a synonym expansion, boosted and finally blended by a
+/// [`Searcher`](crate::Searcher)'s score blender.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation {
+    /// The graphemes of the term, once normalized.
+    pub term: Vec<String>,
+    /// The graphemes of the key that scored best, once normalized.
+    pub key: Vec<String>,
+    /// The key as written.
+    pub original: String,
+    /// Which of the item's keys scored best. Keys have no weights: each is
+    /// scored on its own and the best one counts for the item.
+    pub key_index: usize,
+    pub alignment: Vec<Edit>,
+    pub distance: f64,
+    /// What the distance is divided by: the term's length with Sellers, and
+    /// the longer of the term and key without.
+    pub length: usize,
+    /// The distance over the length, or 0 for a path match, which search
+    /// only scores by its structure.
+    pub text_score: f64,
+    /// The score of an identifier or path match, when it beat the text score.
+    pub structured_score: Option<f64>,
+    /// How many of the term's words sound like one of the key's, when
+    /// phonetic matching is on.
+    pub phonetic_agreement: Option<f64>,
+    pub phonetic_weight: Option<f64>,
+    /// Whether every number of the term matched one of the key, when
+    /// `numeric_tokens` is set.
+    pub numbers_match: Option<bool>,
+    pub boost: Option<f64>,
+    pub boost_mode: Option<BoostMode>,
+    pub expansion: Option<Expansion>,
+    /// The score once blended by a searcher's score blender.
+    pub blended: Option<f64>,
+    pub threshold: f64,
+    /// Whether the key scored well enough to be returned by a search.
+    pub matched: bool,
+    pub score: f64,
+}
+
+/// Explains how `candidate` scores against `term`, as it would in a search.
+pub fn explain<T, U>(
+    term: impl ToString,
+    candidate: T,
+    options: &FuzzyOptions<T, U>,
+) -> Explanation {
+    let mut explanation = explain_item(&term.to_string(), &candidate, options);
+    boost_explanation(&mut explanation, &candidate, options);
+    explanation
+}
+
+pub(crate) fn explain_item<T, U>(
+    term: &str,
+    item: &T,
+    options: &FuzzyOptions<T, U>,
+) -> Explanation {
+    let normal_term = normalize(term.to_string(), options).normal;
+
+    let (candidate, result) = (options.key_selector)(item)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(key_index, key)| {
+            normalize_key(key, options)
+                .into_iter()
+                .map(move |normalized| Candidate {
+                    index: 0,
+                    key_index,
+                    normalized,
+                })
+        })
+        .map(|candidate| {
+            let result = score_candidate(term, &normal_term, &candidate, options);
+            (candidate, result)
+        })
+        .min_by(|(_, a), (_, b)| compare_matches(a, b))
+        .unwrap_or_else(|| {
+            let candidate = Candidate {
+                index: 0,
+                key_index: 0,
+                normalized: normalize(String::new(), options),
+            };
+            let result = score_candidate(term, &normal_term, &candidate, options);
+            (candidate, result)
+        });
+
+    let scorer = Scorer::new(options);
+    let key = &candidate.normalized.normal;
+    let rows = scorer.rows(&normal_term, key);
+    let score_result = scorer.get_score(&rows, key.len() + 1);
+    let alignment = align(&scorer, &normal_term, key, &rows, score_result.score_index);
+
+    let distance = rows[normal_term.len()][score_result.score_index];
+    let length = if scorer.use_sellers {
+        normal_term.len()
+    } else {
+        normal_term.len().max(key.len())
+    };
+
+    // path keys are only scored by their structure
+    let text_score = if options.match_kind == MatchKind::Path {
+        0.0
+    } else {
+        score_result.score
+    };
+
+    let structured_score = structured_match(term, &normal_term, &candidate, options)
+        .filter(|structured| structured.score >= text_score)
+        .map(|structured| structured.score);
+
+    let phonetic_agreement = options.phonetic.map(|algorithm| {
+        phonetic::agreement(
+            &phonetic::encode(term, algorithm),
+            &phonetic::encode(&candidate.normalized.original, algorithm),
+        )
+    });
+
+    let numbers_match = options
+        .numeric_tokens
+        .map(|mode| numeric::all_agree(term, &candidate.normalized.original, mode));
+
+    let score = result.score;
+    let matched = score >= options.threshold;
+
+    Explanation {
+        term: normal_term,
+        key: key.clone(),
+        original: candidate.normalized.original,
+        key_index: candidate.key_index,
+        alignment,
+        distance,
+        length,
+        text_score,
+        structured_score,
+        phonetic_agreement,
+        phonetic_weight: options.phonetic.map(|_| options.phonetic_weight),
+        numbers_match,
+        boost: None,
+        boost_mode: None,
+        expansion: None,
+        blended: None,
+        threshold: options.threshold,
+        matched,
+        score,
+    }
+}
+
+/// Boosts an explanation that made the threshold, as search does once any
+/// synonym penalty has been applied.
+pub(crate) fn boost_explanation<T, U>(
+    explanation: &mut Explanation,
+    item: &T,
+    options: &FuzzyOptions<T, U>,
+) {
+    let Some(boost) = options.boost.as_ref().filter(|_| explanation.matched) else {
+        return;
+    };
+
+    let value = boost(item);
+    explanation.score = match options.boost_mode {
+        BoostMode::Multiply => explanation.score * value,
+        BoostMode::Add => explanation.score + value,
+    };
+    explanation.boost = Some(value);
+    explanation.boost_mode = Some(options.boost_mode);
+}
+
+// Retraces the cheapest path through the matrix, from the cell the score was
+// read from back to the start of the term.
+fn align(
+    scorer: &Scorer,
+    term: &[String],
+    key: &[String],
+    rows: &[Vec<f64>],
+    end: usize,
+) -> Vec<Edit> {
+    let (mut i, mut j) = (term.len(), end);
+    let mut edits = vec![];
+
+    // with Sellers, the match can start anywhere in the key
+    while i > 0 || (j > 0 && !scorer.use_sellers) {
+        let value = rows[i][j];
+        let reaches = |from: f64| (from - value).abs() < EPSILON;
+
+        if i > 0 && j > 0 {
+            let cost = scorer.substitution(&term[i - 1], &key[j - 1]);
+            if reaches(rows[i - 1][j - 1] + cost) {
+                edits.push(Edit {
+                    kind: if cost == 0.0 {
+                        EditKind::Match
+                    } else {
+                        EditKind::Substitute
+                    },
+                    term: term[i - 1].clone(),
+                    key: key[j - 1].clone(),
+                    cost,
+                });
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+
+        let transpose = scorer.costs.transpose;
+        if scorer.use_damerau
+            && i > 1
+            && j > 1
+            && term[i - 1] == key[j - 2]
+            && term[i - 2] == key[j - 1]
+            && reaches(rows[i - 2][j - 2] + transpose)
+        {
+            edits.push(Edit {
+                kind: EditKind::Transpose,
+                term: term[i - 2..i].concat(),
+                key: key[j - 2..j].concat(),
+                cost: transpose,
+            });
+            i -= 2;
+            j -= 2;
+            continue;
+        }
+
+        if j > 0 && (i == 0 || reaches(rows[i][j - 1] + scorer.costs.insert)) {
+            edits.push(Edit {
+                kind: EditKind::Insert,
+                term: String::new(),
+                key: key[j - 1].clone(),
+                cost: scorer.costs.insert,
+            });
+            j -= 1;
+            continue;
+        }
+
+        edits.push(Edit {
+            kind: EditKind::Delete,
+            term: term[i - 1].clone(),
+            key: String::new(),
+            cost: scorer.costs.delete,
+        });
+        i -= 1;
+    }
+
+    edits.reverse();
+    edits
+}
+
+// shows spaces, which would otherwise be lost between the graphemes and in
+// the padding of the table
+fn visible(text: &str) -> String {
+    text.replace(' ', "␣")
+}
+
+fn graphemes(graphemes: &[String]) -> String {
+    graphemes
+        .iter()
+        .map(|grapheme| visible(grapheme))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl fmt::Display for EditKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EditKind::Match => "match",
+            EditKind::Substitute => "substitute",
+            EditKind::Insert => "insert",
+            EditKind::Delete => "delete",
+            EditKind::Transpose => "transpose",
+        };
+        f.pad(name)
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<12}{}", "term", graphemes(&self.term))?;
+        writeln!(f, "{:<12}{}", "key", graphemes(&self.key))?;
+        writeln!(
+            f,
+            "{:<12}{} (key {})",
+            "original", self.original, self.key_index
+        )?;
+        writeln!(f)?;
+
+        let width = |column: fn(&Edit) -> &str, title: &str| {
+            self.alignment
+                .iter()
+                .map(|edit| column(edit).chars().count())
+                .chain([title.chars().count()])
+                .max()
+                .unwrap_or_default()
+                + 2
+        };
+        let term_width = width(|edit| &edit.term, "term");
+        let key_width = width(|edit| &edit.key, "key");
+
+        writeln!(
+            f,
+            "{:<12}{:<term_width$}{:<key_width$}cost",
+            "edit", "term", "key"
+        )?;
+        for edit in &self.alignment {
+            writeln!(
+                f,
+                "{:<12}{:<term_width$}{:<key_width$}{}",
+                edit.kind,
+                visible(&edit.term),
+                visible(&edit.key),
+                edit.cost
+            )?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "{:<12}{}", "distance", self.distance)?;
+        writeln!(f, "{:<12}{}", "length", self.length)?;
+        writeln!(f, "{:<12}{}", "text score", self.text_score)?;
+        if let Some(structured) = self.structured_score {
+            writeln!(f, "{:<12}{}", "structured", structured)?;
+        }
+        if let (Some(agreement), Some(weight)) = (self.phonetic_agreement, self.phonetic_weight) {
+            writeln!(f, "{:<12}{} (weight {})", "phonetic", agreement, weight)?;
+        }
+        if let Some(numbers_match) = self.numbers_match {
+            let numbers = if numbers_match { "match" } else { "differ" };
+            writeln!(f, "{:<12}{}", "numbers", numbers)?;
+        }
+        if let (Some(boost), Some(mode)) = (self.boost, self.boost_mode) {
+            let operator = match mode {
+                BoostMode::Multiply => "×",
+                BoostMode::Add => "+",
+            };
+            writeln!(f, "{:<12}{}{}", "boost", operator, boost)?;
+        }
+        if let Some(expansion) = &self.expansion {
+            let replacements = expansion
+                .replacements
+                .iter()
+                .map(|(from, to)| format!("{from} → {to}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                f,
+                "{:<12}{} (penalty {})",
+                "expansion", replacements, expansion.penalty
+            )?;
+        }
+        if let Some(blended) = self.blended {
+            writeln!(f, "{:<12}{}", "blended", blended)?;
+        }
+
+        let matched = if self.matched {
+            "matched"
+        } else {
+            "not matched"
+        };
+        writeln!(f, "{:<12}{} ({})", "threshold", self.threshold, matched)?;
+        write!(f, "{:<12}{}", "score", self.score)
+    }
+}
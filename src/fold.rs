@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_normalization::UnicodeNormalization;
+
+lazy_static! {
+    static ref TRADITIONAL_TO_SIMPLIFIED: HashMap<char, char> =
+        include_str!("data/ts_characters.txt")
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let (traditional, simplified) = line.split_once('\t')?;
+                Some((traditional.chars().next()?, simplified.chars().next()?))
+            })
+            .collect();
+}
+
+const IDEOGRAPHIC_SPACE: char = '\u{3000}';
+
+/// Maps `grapheme` to its TR39 skeleton, which is shared by every string that
+/// looks the same.
+pub(crate) fn skeleton(grapheme: &str) -> String {
+    unicode_security::skeleton(grapheme).nfc().collect()
+}
+
+/// Folds full-width Latin letters and symbols to ASCII, and half-width
+/// katakana to full-width, leaving other compatibility characters alone.
+pub(crate) fn fold_width(grapheme: &str) -> String {
+    grapheme
+        .chars()
+        .flat_map(|char| match char {
+            IDEOGRAPHIC_SPACE => vec![' '],
+            // the Halfwidth and Fullwidth Forms block
+            '\u{ff00}'..='\u{ffef}' => char.to_string().nfkc().collect(),
+            _ => vec![char],
+        })
+        // half-width voiced sound marks are separate, so compose them with the
+        // kana before them
+        .nfc()
+        .collect()
+}
+
+/// Folds katakana to the hiragana with the same sound.
+pub(crate) fn fold_kana(char: char) -> char {
+    match char {
+        'ァ'..='ヶ' | 'ヽ' | 'ヾ' => {
+            char::from_u32(char as u32 - ('ァ' as u32 - 'ぁ' as u32)).unwrap_or(char)
+        }
+        _ => char,
+    }
+}
+
+/// Folds a Traditional Chinese character to its Simplified form.
+pub(crate) fn fold_chinese_variant(char: char) -> char {
+    TRADITIONAL_TO_SIMPLIFIED
+        .get(&char)
+        .copied()
+        .unwrap_or(char)
+}
+
+/// Removes the accents from `grapheme`, so that `é` becomes `e`.
+///
+/// Letters whose stroke is part of the letter itself rather than a combining
+/// mark, such as `ø` and `ł`, are folded to their base letter as well.
+pub(crate) fn strip_diacritics(grapheme: &str) -> String {
+    grapheme
+        .nfd()
+        .filter(|char| get_general_category(*char) != GeneralCategory::NonspacingMark)
+        .map(strip_stroke)
+        .nfc()
+        .collect()
+}
+
+fn strip_stroke(char: char) -> char {
+    match char {
+        'ø' => 'o',
+        'Ø' => 'O',
+        'ł' => 'l',
+        'Ł' => 'L',
+        'đ' => 'd',
+        'Đ' => 'D',
+        'ħ' => 'h',
+        'Ħ' => 'H',
+        'ŧ' => 't',
+        'Ŧ' => 'T',
+        'ƀ' => 'b',
+        'Ƀ' => 'B',
+        'ɨ' => 'i',
+        'Ɨ' => 'I',
+        _ => char,
+    }
+}
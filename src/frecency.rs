@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::Path,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+
+/// Adjusts the score of a match by something other than its text, such as
+/// how often the item is used. Set on a [`Searcher`](crate::Searcher) with
+/// `score_blender`; matches are then ranked by the blended score.
+pub trait ScoreBlender<T>: Send + Sync {
+    fn blend(&self, item: &T, score: f64) -> f64;
+}
+
+impl<T, F> ScoreBlender<T> for F
+where
+    F: Fn(&T, f64) -> f64 + Send + Sync,
+{
+    fn blend(&self, item: &T, score: f64) -> f64 {
+        self(item, score)
+    }
+}
+
+impl<T, B> ScoreBlender<T> for Arc<B>
+where
+    B: ScoreBlender<T> + ?Sized,
+{
+    fn blend(&self, item: &T, score: f64) -> f64 {
+        (**self).blend(item, score)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum FrecencyError {
+    #[error("failed to read or write frecency: {0}")]
+    Io(#[from] io::Error),
+    #[error("line {0} is not a frecency entry")]
+    Parse(usize),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    // the decayed count as of `updated`
+    count: f64,
+    // seconds since the Unix epoch
+    updated: f64,
+}
+
+/// How often and how recently items were selected, by their `to_string`.
+///
+/// Every selection counts for one, halving every `half_life`. As a
+/// [`ScoreBlender`], the count `c` is blended into the score as `c / (c + 1)`
+/// by `weight`, so items never selected rank lower than they otherwise
+/// would. Selections can be recorded while a searcher uses the store, by
+/// sharing it in an `Arc`.
+#[derive(Debug)]
+pub struct Frecency {
+    entries: RwLock<HashMap<String, Entry>>,
+    half_life: Duration,
+    weight: f64,
+}
+
+impl Default for Frecency {
+    fn default() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            half_life: Duration::from_secs(7 * 24 * 60 * 60),
+            weight: 0.2,
+        }
+    }
+}
+
+fn seconds(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+fn escape(key: &str) -> String {
+    key.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(key: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = key.chars();
+
+    while let Some(char) = chars.next() {
+        match (char, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            _ => unescaped.push(char),
+        }
+    }
+
+    unescaped
+}
+
+impl Frecency {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long a selection takes to count for half as much, a week by
+    /// default.
+    pub fn half_life(mut self, half_life: Duration) -> Self {
+        assert!(!half_life.is_zero(), "half life must be positive");
+        self.half_life = half_life;
+        self
+    }
+
+    /// How much usage counts towards the blended score, between 0 and 1, 0.2
+    /// by default.
+    pub fn weight(mut self, weight: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&weight),
+            "weight must be between 0 and 1, got {weight}"
+        );
+        self.weight = weight;
+        self
+    }
+
+    fn decay(&self, entry: Entry, now: f64) -> f64 {
+        let age = (now - entry.updated).max(0.0);
+        entry.count * 0.5_f64.powf(age / self.half_life.as_secs_f64())
+    }
+
+    /// Records that `key` was selected now.
+    pub fn record(&self, key: impl ToString) {
+        self.record_at(key, SystemTime::now());
+    }
+
+    pub fn record_at(&self, key: impl ToString, time: SystemTime) {
+        let now = seconds(time);
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.entry(key.to_string()).or_insert(Entry {
+            count: 0.0,
+            updated: now,
+        });
+
+        entry.count = self.decay(*entry, now) + 1.0;
+        entry.updated = entry.updated.max(now);
+    }
+
+    /// The decayed count of selections of `key`, as of now.
+    pub fn count(&self, key: &str) -> f64 {
+        self.count_at(key, SystemTime::now())
+    }
+
+    pub fn count_at(&self, key: &str, time: SystemTime) -> f64 {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .map_or(0.0, |entry| self.decay(*entry, seconds(time)))
+    }
+
+    /// Blends the usage of `key` as of `time` into `score`.
+    pub fn blend_at(&self, key: &str, score: f64, time: SystemTime) -> f64 {
+        let count = self.count_at(key, time);
+        (1.0 - self.weight) * score + self.weight * count / (count + 1.0)
+    }
+
+    /// Reads the entries saved in `path` into a store with the default
+    /// settings.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FrecencyError> {
+        let text = fs::read_to_string(path)?;
+        let mut entries = HashMap::new();
+
+        for (number, line) in text.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut columns = line.splitn(3, '\t');
+            let entry = (|| {
+                let updated = columns.next()?.parse().ok()?;
+                let count = columns.next()?.parse().ok()?;
+                Some((unescape(columns.next()?), Entry { count, updated }))
+            })();
+            let (key, entry) = entry.ok_or(FrecencyError::Parse(number + 1))?;
+            entries.insert(key, entry);
+        }
+
+        Ok(Self {
+            entries: RwLock::new(entries),
+            ..Self::default()
+        })
+    }
+
+    /// Writes every entry to `path`, one per line.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), FrecencyError> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        let mut keys = entries.keys().collect::<Vec<_>>();
+        keys.sort();
+
+        let text = keys
+            .into_iter()
+            .map(|key| {
+                let entry = entries[key];
+                format!("{}\t{}\t{}\n", entry.updated, entry.count, escape(key))
+            })
+            .collect::<String>();
+
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+impl<T> ScoreBlender<T> for Frecency
+where
+    T: ToString,
+{
+    fn blend(&self, item: &T, score: f64) -> f64 {
+        self.blend_at(&item.to_string(), score, SystemTime::now())
+    }
+}
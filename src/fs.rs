@@ -0,0 +1,317 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use thiserror::Error;
+
+use crate::{FuzzyOptions, MatchData, MatchKind, SearchResult, Searcher};
+
+#[derive(Error, Debug)]
+pub enum FileSearchError {
+    #[error("failed to walk directory: {0}")]
+    Walk(#[from] ignore::Error),
+    #[error("invalid glob: {0}")]
+    Glob(#[from] globset::Error),
+    #[cfg(feature = "watch")]
+    #[error("failed to watch directory: {0}")]
+    Watch(#[from] notify::Error),
+}
+
+pub struct FileSearchOptions {
+    pub(crate) hidden: bool,
+    pub(crate) ignore_files: bool,
+    pub(crate) follow_links: bool,
+    pub(crate) include: Vec<String>,
+    pub(crate) exclude: Vec<String>,
+    pub(crate) fuzzy_options: FuzzyOptions<PathBuf>,
+}
+
+/// The key a file is indexed under: its path relative to the root, with `/`
+/// separators on every platform.
+pub fn path_key(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl Default for FileSearchOptions {
+    fn default() -> Self {
+        Self {
+            hidden: false,
+            ignore_files: true,
+            follow_links: false,
+            include: vec![],
+            exclude: vec![],
+            fuzzy_options: FuzzyOptions::default_with_key_selector(|path: &PathBuf| {
+                vec![path_key(path)]
+            })
+            .match_kind(MatchKind::Path),
+        }
+    }
+}
+
+impl FileSearchOptions {
+    /// Whether to index hidden files and directories.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Whether to honor `.gitignore`, `.ignore` and git's exclude files.
+    pub fn ignore_files(mut self, ignore_files: bool) -> Self {
+        self.ignore_files = ignore_files;
+        self
+    }
+
+    pub fn follow_links(mut self, follow_links: bool) -> Self {
+        self.follow_links = follow_links;
+        self
+    }
+
+    /// Only index files matching this glob, or any other included glob.
+    pub fn include(mut self, glob: impl Into<String>) -> Self {
+        self.include.push(glob.into());
+        self
+    }
+
+    /// Skip files and directories matching this glob.
+    pub fn exclude(mut self, glob: impl Into<String>) -> Self {
+        self.exclude.push(glob.into());
+        self
+    }
+
+    pub fn fuzzy_options<U>(mut self, fuzzy_options: FuzzyOptions<PathBuf, U>) -> Self {
+        self.fuzzy_options = fuzzy_options.erase_marker();
+        self
+    }
+}
+
+/// Indexes the files below a root directory, by their relative paths.
+pub struct FileSearcher {
+    root: PathBuf,
+    hidden: bool,
+    ignore_files: bool,
+    follow_links: bool,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    // the slot of each file in the searcher, sorted so that the files below
+    // a directory are next to each other
+    files: BTreeMap<PathBuf, usize>,
+    generation: u64,
+    searcher: Searcher<PathBuf>,
+}
+
+fn glob_set(globs: &[String]) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob)?);
+    }
+    builder.build()
+}
+
+impl FileSearcher {
+    pub fn new(
+        root: impl Into<PathBuf>,
+        options: FileSearchOptions,
+    ) -> Result<Self, FileSearchError> {
+        let mut this = Self {
+            root: root.into(),
+            hidden: options.hidden,
+            ignore_files: options.ignore_files,
+            follow_links: options.follow_links,
+            include: if options.include.is_empty() {
+                None
+            } else {
+                Some(glob_set(&options.include)?)
+            },
+            exclude: glob_set(&options.exclude)?,
+            files: BTreeMap::new(),
+            generation: 0,
+            searcher: Searcher::new(vec![], options.fuzzy_options),
+        };
+        this.rescan()?;
+        Ok(this)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn len(&self) -> usize {
+        self.searcher.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.searcher.is_empty()
+    }
+
+    /// Counts the changes made to the index, so that callers can tell when
+    /// results may have changed.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Walks the root again, replacing the index with what is on disk now.
+    pub fn rescan(&mut self) -> Result<(), FileSearchError> {
+        let files = self.walk(None)?;
+        self.searcher.clear();
+        self.files = files.iter().cloned().zip(0..).collect();
+        self.searcher.add(files);
+        self.generation += 1;
+        Ok(())
+    }
+
+    /// Brings the given paths, and everything below them, in line with what
+    /// is on disk without walking the whole root. Paths may be absolute or
+    /// relative to the root.
+    pub fn refresh<P>(&mut self, paths: impl IntoIterator<Item = P>) -> Result<(), FileSearchError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut changed = false;
+
+        for path in paths {
+            let Some(relative) = self.relative_path(path.as_ref()) else {
+                continue;
+            };
+            let relative = relative.as_path();
+
+            let found = if self.root.join(relative).exists() {
+                self.walk(Some(relative))?
+                    .into_iter()
+                    .collect::<HashSet<_>>()
+            } else {
+                HashSet::new()
+            };
+
+            let removed = self
+                .files
+                .range(relative.to_path_buf()..)
+                .take_while(|(file, _)| file.starts_with(relative))
+                .filter(|(file, _)| !found.contains(*file))
+                .map(|(file, _)| file.clone())
+                .collect::<Vec<_>>();
+            let added = found
+                .into_iter()
+                .filter(|file| !self.files.contains_key(file))
+                .collect::<Vec<_>>();
+
+            if !removed.is_empty() {
+                for file in removed {
+                    if let Some(slot) = self.files.remove(&file) {
+                        self.searcher.remove_at(slot);
+                    }
+                }
+                changed = true;
+            }
+
+            if !added.is_empty() {
+                let first = self.searcher.next_slot();
+                self.files.extend(added.iter().cloned().zip(first..));
+                self.searcher.add(added);
+                changed = true;
+            }
+        }
+
+        if self.searcher.compact() {
+            self.files = self
+                .searcher
+                .slots()
+                .map(|(slot, file)| (file.clone(), slot))
+                .collect();
+        }
+
+        if changed {
+            self.generation += 1;
+        }
+
+        Ok(())
+    }
+
+    fn relative_path(&self, path: &Path) -> Option<PathBuf> {
+        if let Ok(relative) = path.strip_prefix(&self.root) {
+            return Some(relative.to_path_buf());
+        }
+
+        if path.is_relative() {
+            return Some(path.to_path_buf());
+        }
+
+        // the root may have been given as a relative path, or through a
+        // symlink, while events carry the real absolute path
+        let root = self.root.canonicalize().ok()?;
+        path.strip_prefix(root).ok().map(Path::to_path_buf)
+    }
+
+    pub(crate) fn is_included(&self, relative: &Path) -> bool {
+        !self.exclude.is_match(relative)
+            && self
+                .include
+                .as_ref()
+                .is_none_or(|include| include.is_match(relative))
+    }
+
+    // Walks the root, or only the part of it at or below `target`, with the
+    // same rules either way.
+    fn walk(&self, target: Option<&Path>) -> Result<Vec<PathBuf>, FileSearchError> {
+        let root = self.root.clone();
+        let exclude = self.exclude.clone();
+        let target = target.map(Path::to_path_buf);
+
+        let walker = WalkBuilder::new(&self.root)
+            .hidden(!self.hidden)
+            .ignore(self.ignore_files)
+            .git_ignore(self.ignore_files)
+            .git_global(self.ignore_files)
+            .git_exclude(self.ignore_files)
+            .parents(self.ignore_files)
+            .require_git(false)
+            .follow_links(self.follow_links)
+            .sort_by_file_path(Path::cmp)
+            .filter_entry(move |entry| {
+                let Ok(relative) = entry.path().strip_prefix(&root) else {
+                    return true;
+                };
+
+                let on_target = target.as_ref().is_none_or(|target| {
+                    relative.starts_with(target) || target.starts_with(relative)
+                });
+                on_target && !exclude.is_match(relative)
+            })
+            .build();
+
+        let mut files = vec![];
+        for entry in walker {
+            let entry = entry?;
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                continue;
+            }
+
+            if let Ok(relative) = entry.path().strip_prefix(&self.root) {
+                if self.is_included(relative) {
+                    files.push(relative.to_path_buf());
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    pub fn search(&self, term: impl ToString) -> Result<Vec<PathBuf>, SearchResult> {
+        self.searcher.search(term)
+    }
+
+    pub fn search_data(
+        &self,
+        term: impl ToString,
+    ) -> Result<Vec<MatchData<PathBuf>>, SearchResult> {
+        self.searcher.search_data(term)
+    }
+}
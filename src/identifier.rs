@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{util::normalize, FuzzyOptions};
+
+const SEPARATORS: [&str; 7] = ["_", "-", "/", "\\", ".", ":", " "];
+
+// a match at the start of a word or right after the previous match
+const STRONG_BONUS: f64 = 1.0;
+// a match in the middle of a word, after a gap
+const WEAK_BONUS: f64 = 0.5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct IdentifierMatch {
+    pub(crate) score: f64,
+    // byte ranges of the key, as (index, length)
+    pub(crate) spans: Vec<(usize, usize)>,
+}
+
+struct Grapheme<'k> {
+    index: usize,
+    text: String,
+    raw: &'k str,
+    word_start: bool,
+}
+
+fn is_separator(grapheme: &str) -> bool {
+    SEPARATORS.contains(&grapheme) || grapheme.trim().is_empty()
+}
+
+fn is_upper(grapheme: &str) -> bool {
+    grapheme.chars().any(char::is_uppercase)
+}
+
+fn is_lower(grapheme: &str) -> bool {
+    grapheme.chars().any(char::is_lowercase)
+}
+
+fn is_digit(grapheme: &str) -> bool {
+    grapheme.chars().all(|c| c.is_numeric())
+}
+
+/// Splits an identifier into its words, as byte ranges of `key`.
+///
+/// Words are separated by `_`, `-`, `.`, `:`, whitespace and path separators,
+/// and by case changes: `HTTPRequestContext` is `HTTP`, `Request`, `Context`.
+pub(crate) fn split_words(key: &str) -> Vec<(usize, usize)> {
+    let graphemes = key.grapheme_indices(true).collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut start = None;
+
+    for (i, &(index, grapheme)) in graphemes.iter().enumerate() {
+        if is_separator(grapheme) {
+            if let Some(start) = start.take() {
+                words.push((start, index - start));
+            }
+            continue;
+        }
+
+        let boundary = match (
+            i.checked_sub(1).map(|i| graphemes[i].1),
+            graphemes.get(i + 1),
+        ) {
+            (Some(previous), next) if !is_separator(previous) => {
+                // fooBar
+                (is_lower(previous) && is_upper(grapheme))
+                    // HTTPRequest, at the "R"
+                    || (is_upper(previous)
+                        && is_upper(grapheme)
+                        && next.is_some_and(|&(_, next)| is_lower(next)))
+                    // file2, v2beta
+                    || is_digit(previous) != is_digit(grapheme)
+            }
+            _ => false,
+        };
+
+        if boundary {
+            if let Some(start) = start.take() {
+                words.push((start, index - start));
+            }
+        }
+
+        start.get_or_insert(index);
+    }
+
+    if let Some(start) = start {
+        words.push((start, key.len() - start));
+    }
+
+    words
+}
+
+// The normalized graphemes of `key`, as the pipeline leaves them, each with
+// the grapheme of `key` it came from.
+fn graphemes<'k, T, U>(key: &'k str, options: &FuzzyOptions<T, U>) -> Vec<Grapheme<'k>> {
+    let starts = split_words(key)
+        .into_iter()
+        .map(|(start, _)| start)
+        .collect::<Vec<_>>();
+    let raws = key.grapheme_indices(true).collect::<HashMap<_, _>>();
+    let normalized = normalize(key.to_string(), options);
+
+    normalized
+        .normal
+        .into_iter()
+        .zip(normalized.map)
+        .map(|(text, index)| Grapheme {
+            index,
+            text,
+            raw: raws.get(&index).copied().unwrap_or_default(),
+            word_start: starts.contains(&index),
+        })
+        .collect()
+}
+
+/// Scores `term` as a subsequence of `key`, rewarding letters that land on
+/// word starts or continue the previous match, so that `gcfg` finds
+/// `get_config_file` and `HRC` finds `HttpRequestContext`.
+///
+/// Returns `None` unless every letter of the term can be matched in order.
+pub(crate) fn score_identifier<T, U>(
+    term: &str,
+    key: &str,
+    options: &FuzzyOptions<T, U>,
+) -> Option<IdentifierMatch> {
+    let term = graphemes(term, options)
+        .into_iter()
+        .filter(|grapheme| !is_separator(grapheme.raw))
+        .map(|grapheme| grapheme.text)
+        .collect::<Vec<_>>();
+    let key = graphemes(key, options);
+
+    if term.is_empty() {
+        return Some(IdentifierMatch {
+            score: 1.0,
+            spans: vec![],
+        });
+    }
+
+    // best[i][j] is the best total for term[..=i] with term[i] on key[j], and
+    // from[i][j] the key position term[i - 1] was matched on for that total
+    let mut best = vec![vec![f64::NEG_INFINITY; key.len()]; term.len()];
+    let mut from = vec![vec![0; key.len()]; term.len()];
+
+    for (i, letter) in term.iter().enumerate() {
+        // the best total for term[..i] ending strictly before key[j - 1]
+        let mut running: Option<(f64, usize)> = None;
+
+        for (j, grapheme) in key.iter().enumerate() {
+            if i > 0 && j >= 2 && best[i - 1][j - 2] > running.map_or(f64::NEG_INFINITY, |r| r.0) {
+                running = Some((best[i - 1][j - 2], j - 2));
+            }
+
+            if &grapheme.text != letter || is_separator(grapheme.raw) {
+                continue;
+            }
+
+            let bonus = |consecutive: bool| {
+                if grapheme.word_start || consecutive {
+                    STRONG_BONUS
+                } else {
+                    WEAK_BONUS
+                }
+            };
+
+            if i == 0 {
+                best[i][j] = bonus(false);
+                continue;
+            }
+
+            if j > 0 && best[i - 1][j - 1].is_finite() {
+                best[i][j] = best[i - 1][j - 1] + bonus(true);
+                from[i][j] = j - 1;
+            }
+
+            if let Some((total, previous)) = running {
+                if total + bonus(false) > best[i][j] {
+                    best[i][j] = total + bonus(false);
+                    from[i][j] = previous;
+                }
+            }
+        }
+    }
+
+    let last = term.len() - 1;
+    let (mut j, total) = best[last]
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, total)| total.is_finite())
+        .fold(None, |best: Option<(usize, f64)>, (j, total)| match best {
+            Some((_, best_total)) if best_total >= total => best,
+            _ => Some((j, total)),
+        })?;
+
+    let mut positions = vec![j];
+    for i in (1..term.len()).rev() {
+        j = from[i][j];
+        positions.push(j);
+    }
+    positions.reverse();
+
+    let mut spans: Vec<(usize, usize)> = vec![];
+    for position in positions {
+        let grapheme = &key[position];
+        match spans.last_mut() {
+            // a grapheme the pipeline expanded, such as `ß` to `ss`
+            Some((index, length)) if grapheme.index < *index + *length => {}
+            Some((index, length)) if *index + *length == grapheme.index => {
+                *length += grapheme.raw.len();
+            }
+            _ => spans.push((grapheme.index, grapheme.raw.len())),
+        }
+    }
+
+    Some(IdentifierMatch {
+        score: total / (term.len() as f64 * STRONG_BONUS),
+        spans,
+    })
+}
@@ -0,0 +1,274 @@
+use crate::{
+    bktree::BkTree,
+    search_core, search_core_group,
+    trie::{Trie, TrieMatch},
+    BruteForceIndex, FuzzyOptions, QGramIndex, SymSpellIndex,
+};
+
+/// Which index a [`Searcher`](crate::Searcher) looks its candidates up in,
+/// when the choice is made at runtime, for
+/// [`Searcher::with_index_kind`](crate::Searcher::with_index_kind).
+pub enum IndexKind {
+    /// A trie of the keys, which suits every option.
+    Trie,
+    /// A symmetric delete index, which is faster for correcting words against
+    /// a large vocabulary. It only finds keys close to the whole term, so it
+    /// is meant for `use_sellers(false)`.
+    SymSpell(SymSpellIndex),
+    /// A BK-tree of the keys, which finds keys close to the whole term
+    /// without the trie's walk. It is meant for `use_sellers(false)`.
+    BkTree,
+    /// An inverted index of q-grams, which narrows down the keys to score for
+    /// very large sets of candidates without missing any match.
+    QGram(QGramIndex),
+    /// Scores every key of every item, as a reference for the others.
+    BruteForce,
+}
+
+impl IndexKind {
+    /// An empty index of this kind, for
+    /// [`Searcher::with_index`](crate::Searcher::with_index).
+    pub fn into_index<T>(self) -> Box<dyn Index<T>> {
+        match self {
+            IndexKind::Trie => Box::new(Trie::default()),
+            IndexKind::SymSpell(symspell) => Box::new(symspell),
+            IndexKind::BkTree => Box::new(BkTree::new()),
+            IndexKind::QGram(qgram) => Box::new(qgram),
+            IndexKind::BruteForce => Box::new(BruteForceIndex::new()),
+        }
+    }
+}
+
+/// Where a [`Searcher`](crate::Searcher) looks up the keys of its candidates.
+///
+/// Every backend finds the same matches for the options it suits, so one can
+/// be swapped for another without changing how the searcher is used. Items
+/// are numbered by their position in the searcher, and removed items are
+/// `None` in the `items` a query is given.
+///
+/// Backends outside the crate read keys with [`FuzzyOptions::keys`] and score
+/// the items they find with [`TrieMatch::score_item`]:
+///
+/// ```
+/// use ffrs::{FuzzyOptions, Index, Searcher, TrieMatch};
+///
+/// // only finds items whose first key starts like the term
+/// #[derive(Default)]
+/// struct ByInitial {
+///     initials: Vec<Option<char>>,
+/// }
+///
+/// impl<T> Index<T> for ByInitial {
+///     fn add(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+///         let initial = options.keys(item).first().and_then(|key| key.chars().next());
+///         self.initials.resize(index + 1, None);
+///         self.initials[index] = initial.map(|initial| initial.to_ascii_lowercase());
+///     }
+///
+///     fn remove(&mut self, index: usize, _item: &T, _options: &FuzzyOptions<T>) {
+///         self.initials[index] = None;
+///     }
+///
+///     fn clear(&mut self) {
+///         self.initials.clear();
+///     }
+///
+///     fn query(
+///         &self,
+///         term: &str,
+///         items: &[Option<T>],
+///         options: &FuzzyOptions<T>,
+///     ) -> Vec<TrieMatch> {
+///         let initial = term.chars().next().map(|initial| initial.to_ascii_lowercase());
+///         let mut matches = items
+///             .iter()
+///             .enumerate()
+///             .filter(|&(index, _)| self.initials[index] == initial)
+///             .filter_map(|(index, item)| {
+///                 TrieMatch::score_item(term, index, item.as_ref()?, options)
+///             })
+///             .collect::<Vec<_>>();
+///         TrieMatch::sort(&mut matches, options);
+///         matches
+///     }
+/// }
+///
+/// let searcher = Searcher::with_index(
+///     vec!["Hello", "Jello", "Help"],
+///     FuzzyOptions::default(),
+///     ByInitial::default(),
+/// );
+/// assert_eq!(searcher.search("hello"), Ok(vec!["Hello", "Help"]));
+/// ```
+pub trait Index<T> {
+    /// Indexes `items`, numbered from 0, in place of whatever was indexed.
+    fn build(&mut self, items: &[T], options: &FuzzyOptions<T>) {
+        self.clear();
+        for (index, item) in items.iter().enumerate() {
+            self.add(index, item, options);
+        }
+    }
+
+    fn add(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>);
+
+    fn remove(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>);
+
+    /// Removes every key, keeping the settings.
+    fn clear(&mut self);
+
+    /// The matches of `term` that reach the threshold on their text, sorted.
+    fn query(&self, term: &str, items: &[Option<T>], options: &FuzzyOptions<T>) -> Vec<TrieMatch>;
+
+    /// The first `k` matches [`query`](Self::query) would return. This is a
+    /// full query cut to `k` matches, which none of the crate's backends
+    /// improve on, but one that can stop looking early may override it.
+    fn query_top_k(
+        &self,
+        term: &str,
+        k: usize,
+        items: &[Option<T>],
+        options: &FuzzyOptions<T>,
+    ) -> Vec<TrieMatch> {
+        let mut results = self.query(term, items, options);
+        results.truncate(k);
+        results
+    }
+
+    /// What [`query`](Self::query) would return for each of `terms`, which
+    /// share a prefix. Backends that can share work between such terms
+    /// override this.
+    fn query_group(
+        &self,
+        terms: &[&str],
+        items: &[Option<T>],
+        options: &FuzzyOptions<T>,
+    ) -> Vec<Vec<TrieMatch>> {
+        terms
+            .iter()
+            .map(|term| self.query(term, items, options))
+            .collect()
+    }
+}
+
+// so that the backend can be chosen at runtime
+impl<T, I: Index<T> + ?Sized> Index<T> for Box<I> {
+    fn build(&mut self, items: &[T], options: &FuzzyOptions<T>) {
+        (**self).build(items, options);
+    }
+
+    fn add(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        (**self).add(index, item, options);
+    }
+
+    fn remove(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        (**self).remove(index, item, options);
+    }
+
+    fn clear(&mut self) {
+        (**self).clear();
+    }
+
+    fn query(&self, term: &str, items: &[Option<T>], options: &FuzzyOptions<T>) -> Vec<TrieMatch> {
+        (**self).query(term, items, options)
+    }
+
+    fn query_top_k(
+        &self,
+        term: &str,
+        k: usize,
+        items: &[Option<T>],
+        options: &FuzzyOptions<T>,
+    ) -> Vec<TrieMatch> {
+        (**self).query_top_k(term, k, items, options)
+    }
+
+    fn query_group(
+        &self,
+        terms: &[&str],
+        items: &[Option<T>],
+        options: &FuzzyOptions<T>,
+    ) -> Vec<Vec<TrieMatch>> {
+        (**self).query_group(terms, items, options)
+    }
+}
+
+impl<T> Index<T> for Trie {
+    fn add(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        Trie::add(self, index, item, options);
+    }
+
+    fn remove(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        Trie::remove(self, index, item, options);
+    }
+
+    fn clear(&mut self) {
+        *self = Trie::default();
+    }
+
+    fn query(&self, term: &str, _items: &[Option<T>], options: &FuzzyOptions<T>) -> Vec<TrieMatch> {
+        search_core(term, self, options)
+    }
+
+    fn query_group(
+        &self,
+        terms: &[&str],
+        _items: &[Option<T>],
+        options: &FuzzyOptions<T>,
+    ) -> Vec<Vec<TrieMatch>> {
+        search_core_group(terms, self, options)
+    }
+}
+
+impl<T> Index<T> for SymSpellIndex {
+    fn add(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        SymSpellIndex::add(self, index, item, options);
+    }
+
+    fn remove(&mut self, index: usize, _item: &T, options: &FuzzyOptions<T>) {
+        SymSpellIndex::remove(self, index, options);
+    }
+
+    fn clear(&mut self) {
+        SymSpellIndex::clear(self);
+    }
+
+    fn query(&self, term: &str, _items: &[Option<T>], options: &FuzzyOptions<T>) -> Vec<TrieMatch> {
+        self.search(term, options)
+    }
+}
+
+impl<T> Index<T> for BkTree {
+    fn add(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        BkTree::add(self, index, item, options);
+    }
+
+    fn remove(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        BkTree::remove(self, index, item, options);
+    }
+
+    fn clear(&mut self) {
+        BkTree::clear(self);
+    }
+
+    fn query(&self, term: &str, _items: &[Option<T>], options: &FuzzyOptions<T>) -> Vec<TrieMatch> {
+        self.search(term, options)
+    }
+}
+
+impl<T> Index<T> for QGramIndex {
+    fn add(&mut self, index: usize, item: &T, options: &FuzzyOptions<T>) {
+        QGramIndex::add(self, index, item, options);
+    }
+
+    fn remove(&mut self, index: usize, _item: &T, _options: &FuzzyOptions<T>) {
+        QGramIndex::remove(self, index);
+    }
+
+    fn clear(&mut self) {
+        QGramIndex::clear(self);
+    }
+
+    fn query(&self, term: &str, _items: &[Option<T>], options: &FuzzyOptions<T>) -> Vec<TrieMatch> {
+        self.search(term, options)
+    }
+}
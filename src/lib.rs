@@ -1,56 +1,256 @@
+mod bktree;
+mod brute_force;
+mod case;
+mod confusable;
+mod costs;
+mod explain;
+mod fold;
+mod frecency;
+#[cfg(feature = "fs")]
+mod fs;
+mod identifier;
+mod index;
+mod normalizer;
+mod numeric;
 mod options;
+mod path;
+pub mod phonetic;
+mod qgram;
+mod score;
 mod searcher;
+mod stop_words;
+mod suggest;
+mod symspell;
+mod synonyms;
 #[cfg(test)]
 mod tests;
+mod transliterate;
 mod trie;
 mod util;
+#[cfg(feature = "watch")]
+mod watch;
 
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
+pub use bktree::*;
+pub use brute_force::*;
+pub use confusable::*;
+pub use costs::*;
+pub use explain::*;
+pub use frecency::*;
+#[cfg(feature = "fs")]
+pub use fs::*;
+pub use index::*;
+pub use normalizer::*;
 pub use options::*;
+pub use qgram::*;
+use score::Scorer;
 pub use searcher::*;
+pub use stop_words::*;
+pub use suggest::*;
+pub use symspell::*;
+pub use synonyms::*;
 use thiserror::Error;
+use trie::{compare_by, compare_matches, Candidate};
+pub use trie::{Trie, TrieMatch};
+use util::{normalize, normalize_key};
+#[cfg(feature = "watch")]
+pub use watch::*;
 
 #[derive(PartialEq, Debug)]
-pub struct MatchData<T>
-where
-    T: PartialEq + Debug,
-{
+pub struct MatchData<T> {
     pub item: T,
     pub original: String,
     pub key: String,
     pub score: f64,
     pub match_index: usize,
     pub match_length: usize,
+    pub spans: Vec<(usize, usize)>,
+    /// How the query was rewritten by a [`Synonyms`] dictionary, when the
+    /// match is of an expanded form.
+    pub expansion: Option<Expansion>,
+    /// The value of the options' `boost` for the item, which the score
+    /// includes.
+    pub boost: Option<f64>,
+}
+
+impl<T> MatchData<T> {
+    pub(crate) fn from_match(item: T, result: TrieMatch) -> Self {
+        Self {
+            item,
+            original: result.original,
+            key: result.key,
+            score: result.score,
+            match_index: result.match_index,
+            match_length: result.match_length,
+            spans: result.spans,
+            expansion: result.expansion,
+            boost: result.boost,
+        }
+    }
 }
 
-#[allow(unused_variables)]
 pub fn fuzzy<T, U>(term: impl ToString, candidate: T, options: &FuzzyOptions<T, U>) -> f64 {
-    todo!()
+    let term = term.to_string();
+    let normal_term = normalize(term.clone(), options).normal;
+
+    (options.key_selector)(&candidate)
+        .into_iter()
+        .flat_map(|key| normalize_key(key, options))
+        .map(|normalized| {
+            let candidate = Candidate {
+                index: 0,
+                key_index: 0,
+                normalized,
+            };
+            score_candidate(&term, &normal_term, &candidate, options).score
+        })
+        .fold(0.0, f64::max)
 }
 
-#[allow(unused_variables)]
 pub fn fuzzy_data<T, U>(
     term: impl ToString,
     candidate: T,
     options: &FuzzyOptions<String, U>,
-) -> MatchData<String> {
-    todo!()
+) -> MatchData<String>
+where
+    T: ToString,
+{
+    let term = term.to_string();
+    let normal_term = normalize(term.clone(), options).normal;
+    let candidate = candidate.to_string();
+    let result = fuzzy_match(&term, &normal_term, 0, 0, candidate.clone(), options);
+
+    MatchData::from_match(candidate, result)
+}
+
+/// Scores every normalized form of a single key, keeping the best.
+pub(crate) fn fuzzy_match<T, U>(
+    term: &str,
+    normal_term: &[String],
+    index: usize,
+    key_index: usize,
+    key: String,
+    options: &FuzzyOptions<T, U>,
+) -> TrieMatch {
+    normalize_key(key, options)
+        .into_iter()
+        .map(|normalized| {
+            let candidate = Candidate {
+                index,
+                key_index,
+                normalized,
+            };
+            score_candidate(term, normal_term, &candidate, options)
+        })
+        .min_by(compare_matches)
+        .expect("a key has at least one normalized form")
+}
+
+fn score_candidate<T, U>(
+    term: &str,
+    normal_term: &[String],
+    candidate: &Candidate,
+    options: &FuzzyOptions<T, U>,
+) -> TrieMatch {
+    let result = if options.match_kind == MatchKind::Path {
+        TrieMatch::new(candidate, 0.0, 0, 0, normal_term.len())
+    } else {
+        let (score, start, length) = Scorer::new(options).score(normal_term, &candidate.normalized);
+        TrieMatch::new(candidate, score, start, length, normal_term.len())
+    };
+
+    // on a tie, the structured match has the more useful spans
+    let mut result = match structured_match(term, normal_term, candidate, options) {
+        Some(structured) if compare_matches(&structured, &result).is_le() => structured,
+        _ => result,
+    };
+
+    if let Some(algorithm) = options.phonetic {
+        blend_phonetic(
+            &mut result,
+            &phonetic::encode(term, algorithm),
+            algorithm,
+            options,
+        );
+    }
+
+    if let Some(mode) = options.numeric_tokens {
+        if !numeric::all_agree(term, &result.original, mode) {
+            result.score = 0.0;
+        }
+    }
+
+    result
+}
+
+/// Blends how many of the term's words sound like one of the key's words into
+/// the score of a match.
+fn blend_phonetic<T, U>(
+    result: &mut TrieMatch,
+    term_codes: &[Vec<String>],
+    algorithm: PhoneticAlgorithm,
+    options: &FuzzyOptions<T, U>,
+) {
+    let key_codes = phonetic::encode(&result.original, algorithm);
+    let agreement = phonetic::agreement(term_codes, &key_codes);
+    let weight = options.phonetic_weight;
+
+    result.score = (1.0 - weight) * result.score + weight * agreement;
+}
+
+/// Matches a key by its structure rather than by plain edit distance, for the
+/// match kinds that have one.
+fn structured_match<T, U>(
+    term: &str,
+    normal_term: &[String],
+    candidate: &Candidate,
+    options: &FuzzyOptions<T, U>,
+) -> Option<TrieMatch> {
+    let original = &candidate.normalized.original;
+    let (score, spans) = match options.match_kind {
+        MatchKind::Fuzzy => return None,
+        MatchKind::Identifier => {
+            let result = identifier::score_identifier(term, original, options)?;
+            (result.score, result.spans)
+        }
+        MatchKind::Path => {
+            let result = path::score_path(term, original, options)?;
+            (result.score, result.spans)
+        }
+    };
+
+    let mut result = TrieMatch::new(candidate, score, 0, 0, normal_term.len());
+    result.match_index = spans.first().map_or(0, |span| span.0);
+    result.match_length = spans
+        .last()
+        .map_or(0, |(index, length)| index + length - result.match_index);
+    result.spans = spans;
+
+    Some(result)
 }
 
 #[derive(Error, Debug, PartialEq)]
 pub enum SearchResult {}
 
-#[allow(unused_variables)]
 pub fn search<T, U>(
     term: impl ToString,
     candidates: Vec<T>,
     options: &FuzzyOptions<T, U>,
 ) -> Result<Vec<T>, SearchResult> {
-    todo!()
+    let trie = Trie::new(0, &candidates, options);
+    let mut results = search_core(term, &trie, options);
+    boost_matches(&mut results, |index| candidates.get(index), options);
+    let mut items = candidates.into_iter().map(Some).collect::<Vec<_>>();
+
+    Ok(
+        resolve_matches(results, |index| items[index].take(), options)
+            .into_iter()
+            .map(|data| data.item)
+            .collect(),
+    )
 }
 
-#[allow(unused_variables)]
 pub fn search_data<T, U>(
     term: impl ToString,
     candidates: Vec<T>,
@@ -59,5 +259,180 @@ pub fn search_data<T, U>(
 where
     T: PartialEq + Debug,
 {
-    todo!()
+    let trie = Trie::new(0, &candidates, options);
+    let mut results = search_core(term, &trie, options);
+    boost_matches(&mut results, |index| candidates.get(index), options);
+    let mut items = candidates.into_iter().map(Some).collect::<Vec<_>>();
+
+    Ok(resolve_matches(
+        results,
+        |index| items[index].take(),
+        options,
+    ))
+}
+
+pub(crate) fn search_core<T, U>(
+    term: impl ToString,
+    trie: &Trie,
+    options: &FuzzyOptions<T, U>,
+) -> Vec<TrieMatch> {
+    let term = term.to_string();
+    let normal_term = normalize(term.clone(), options).normal;
+    // path segments are scored separately, so the trie walk can't help there
+    let results = match options.match_kind {
+        MatchKind::Path => HashMap::new(),
+        _ => trie.search(&normal_term, options),
+    };
+
+    finish_search(&term, &normal_term, results, trie, options)
+}
+
+/// Searches for several terms with a single walk of the trie, see
+/// [`Trie::search_group`].
+pub(crate) fn search_core_group<T, U>(
+    terms: &[&str],
+    trie: &Trie,
+    options: &FuzzyOptions<T, U>,
+) -> Vec<Vec<TrieMatch>> {
+    let normal_terms = terms
+        .iter()
+        .map(|term| normalize(term.to_string(), options).normal)
+        .collect::<Vec<_>>();
+    let walks = match options.match_kind {
+        MatchKind::Path => vec![HashMap::new(); terms.len()],
+        _ => trie.search_group(&normal_terms, options),
+    };
+
+    terms
+        .iter()
+        .zip(&normal_terms)
+        .zip(walks)
+        .map(|((term, normal_term), results)| {
+            finish_search(term, normal_term, results, trie, options)
+        })
+        .collect()
+}
+
+/// Adds the structured and phonetic matches the trie walk can't find to its
+/// results, and finishes them.
+fn finish_search<T, U>(
+    term: &str,
+    normal_term: &[String],
+    mut results: HashMap<usize, TrieMatch>,
+    trie: &Trie,
+    options: &FuzzyOptions<T, U>,
+) -> Vec<TrieMatch> {
+    if options.match_kind != MatchKind::Fuzzy {
+        for candidate in trie.candidates() {
+            let Some(result) = structured_match(term, normal_term, candidate, options) else {
+                continue;
+            };
+
+            let better = results
+                .get(&result.index)
+                .is_none_or(|existing| compare_matches(&result, existing).is_le());
+            if result.score >= options.threshold && better {
+                results.insert(result.index, result);
+            }
+        }
+    }
+
+    // a key can sound like the term while being too far from it to survive
+    // the trie walk, so its phonetic codes are looked up as well
+    if let Some(algorithm) = options.phonetic {
+        let codes = phonetic::encode(term, algorithm);
+        for result in results.values_mut() {
+            blend_phonetic(result, &codes, algorithm, options);
+        }
+
+        for candidate in trie.phonetic_candidates(&codes) {
+            let result = score_candidate(term, normal_term, candidate, options);
+            let better = results
+                .get(&result.index)
+                .is_none_or(|existing| compare_matches(&result, existing).is_le());
+            if better {
+                results.insert(result.index, result);
+            }
+        }
+
+        results.retain(|_, result| result.score >= options.threshold);
+    }
+
+    // numbers match whole or not at all, however close the rest of the key is
+    if let Some(mode) = options.numeric_tokens {
+        results.retain(|_, result| numeric::all_agree(term, &result.original, mode));
+    }
+
+    finish_matches(results, options)
+}
+
+/// Sorts the matches found for a term.
+pub(crate) fn finish_matches<T, U>(
+    results: HashMap<usize, TrieMatch>,
+    options: &FuzzyOptions<T, U>,
+) -> Vec<TrieMatch> {
+    let mut results = results.into_values().collect::<Vec<_>>();
+    sort_matches(&mut results, options);
+    results
+}
+
+/// Boosts matches that made the threshold, once any synonym penalty has been
+/// applied, and sorts them again.
+pub(crate) fn boost_matches<'a, T: 'a, U>(
+    results: &mut [TrieMatch],
+    item: impl Fn(usize) -> Option<&'a T>,
+    options: &FuzzyOptions<T, U>,
+) {
+    let Some(boost) = &options.boost else {
+        return;
+    };
+
+    for result in results.iter_mut() {
+        if let Some(item) = item(result.index) {
+            let value = boost(item);
+            result.score = match options.boost_mode {
+                BoostMode::Multiply => result.score * value,
+                BoostMode::Add => result.score + value,
+            };
+            result.boost = Some(value);
+        }
+    }
+
+    sort_matches(results, options);
+}
+
+pub(crate) fn sort_matches<T, U>(results: &mut [TrieMatch], options: &FuzzyOptions<T, U>) {
+    let tie_breaks = &options.tie_breaks;
+
+    match options.sort_by {
+        // custom comparators are applied once matches have their items, see
+        // `resolve_matches`
+        SortKind::BestMatch | SortKind::Custom(_) => {
+            results.sort_by(|a, b| compare_by(a, b, tie_breaks))
+        }
+        SortKind::InsertOrder => results.sort_by_key(|result| result.index),
+        SortKind::ByKey => {
+            results.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| compare_by(a, b, tie_breaks)))
+        }
+    }
+}
+
+/// Pairs sorted matches with their items, applying a custom sort if there is
+/// one.
+pub(crate) fn resolve_matches<T, U>(
+    results: Vec<TrieMatch>,
+    mut item: impl FnMut(usize) -> Option<T>,
+    options: &FuzzyOptions<T, U>,
+) -> Vec<MatchData<T>> {
+    let mut data = results
+        .into_iter()
+        .filter_map(|result| Some(MatchData::from_match(item(result.index)?, result)))
+        .collect::<Vec<_>>();
+
+    // the sort is stable, so the tie-break chain settles what it finds equal
+    if let SortKind::Custom(compare) = &options.sort_by {
+        data.sort_by(compare);
+    }
+
+    data
 }
@@ -0,0 +1,463 @@
+use std::{fmt, sync::Arc};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    case::change_case,
+    fold::{fold_chinese_variant, fold_kana, fold_width, skeleton, strip_diacritics},
+    numeric::digit_value,
+    CaseMode, FuzzyOptions, StopWords,
+};
+
+lazy_static! {
+    static ref WHITESPACE_REGEX: Regex = Regex::new(r"^\s+$").unwrap();
+    static ref NON_WORD_REGEX: Regex =
+        Regex::new(r#"^[`~!@#$%^&*()\-=_+{}\[\]|\\;':",./<>?]+$"#).unwrap();
+}
+
+/// A piece of text being normalized, with the byte index in the original
+/// text where the grapheme it came from starts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub text: String,
+    pub offset: usize,
+}
+
+impl Segment {
+    pub fn new(text: impl Into<String>, offset: usize) -> Self {
+        Self {
+            text: text.into(),
+            offset,
+        }
+    }
+}
+
+/// A step of normalization.
+///
+/// A pipeline starts with one segment per grapheme of the original text, and
+/// each stage maps the segments to new ones. A stage that replaces, expands or
+/// drops text keeps highlighting right as long as every segment it produces
+/// carries the offset of the segment it came from.
+pub trait Normalizer: Send + Sync {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment>;
+}
+
+impl<F> Normalizer for F
+where
+    F: Fn(Vec<Segment>) -> Vec<Segment> + Send + Sync,
+{
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        self(segments)
+    }
+}
+
+/// The stages a key or term goes through before it is scored, in order.
+///
+/// The stages built from the options can be extended with custom ones:
+///
+/// ```
+/// use ffrs::{fuzzy, FuzzyOptions, Pipeline, Segment};
+///
+/// let options = FuzzyOptions::default();
+/// let pipeline = Pipeline::new()
+///     .then(|segments: Vec<Segment>| {
+///         segments
+///             .into_iter()
+///             .map(|segment| match segment.text.as_str() {
+///                 "&" => Segment::new("and", segment.offset),
+///                 _ => segment,
+///             })
+///             .collect()
+///     })
+///     .chain(Pipeline::from_options(&options));
+///
+/// let options = options.pipeline(pipeline);
+/// assert_eq!(fuzzy("salt and pepper", "Salt & Pepper", &options), 1.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    stages: Vec<Arc<dyn Normalizer>>,
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages.len())
+            .finish()
+    }
+}
+
+impl Pipeline {
+    /// A pipeline without any stages, which leaves every grapheme as it is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The stages that `options` ask for, as used when no pipeline is set.
+    pub fn from_options<T, U>(options: &FuzzyOptions<T, U>) -> Self {
+        let mut pipeline = Self::new();
+
+        if options.ignore_case {
+            pipeline = pipeline.then(ChangeCase(options.case_mode));
+        }
+        if options.fold_width {
+            pipeline = pipeline.then(FoldWidth);
+        }
+        if options.fold_kana {
+            pipeline = pipeline.then(FoldKana);
+        }
+        if options.fold_chinese_variants {
+            pipeline = pipeline.then(FoldChineseVariants);
+        }
+        if options.strip_diacritics {
+            pipeline = pipeline.then(StripDiacritics);
+        }
+        if options.fold_confusables {
+            pipeline = pipeline.then(FoldConfusables(
+                options.ignore_case.then_some(options.case_mode),
+            ));
+        }
+
+        pipeline = if options.use_separated_unicode {
+            pipeline.then(Decompose)
+        } else {
+            pipeline.then(SplitGraphemes)
+        };
+
+        if options.numeric_tokens.is_some() {
+            pipeline = pipeline.then(JoinNumbers);
+        }
+
+        if options.normalize_whitespace {
+            pipeline = pipeline.then(CollapseWhitespace);
+        }
+        if options.ignore_symbols {
+            pipeline = pipeline.then(RemoveSymbols);
+        }
+        if let Some(stop_words) = &options.stop_words {
+            pipeline = pipeline.then(RemoveStopWords(stop_words.clone()));
+        }
+        if options.min_token_length > 1 {
+            pipeline = pipeline.then(MinTokenLength(options.min_token_length));
+        }
+
+        pipeline
+    }
+
+    /// Adds a stage at the end of the pipeline.
+    pub fn then(mut self, stage: impl Normalizer + 'static) -> Self {
+        self.stages.push(Arc::new(stage));
+        self
+    }
+
+    /// Adds the stages of `other` at the end of the pipeline.
+    pub fn chain(mut self, other: Pipeline) -> Self {
+        self.stages.extend(other.stages);
+        self
+    }
+
+    pub fn run(&self, text: &str) -> Vec<Segment> {
+        let segments = text
+            .grapheme_indices(true)
+            .map(|(offset, grapheme)| Segment::new(grapheme, offset))
+            .collect();
+
+        self.stages
+            .iter()
+            .fold(segments, |segments, stage| stage.normalize(segments))
+    }
+}
+
+// Maps the text of every segment, keeping its offset.
+fn map_text(segments: Vec<Segment>, map: impl Fn(&str) -> String) -> Vec<Segment> {
+    segments
+        .into_iter()
+        .map(|segment| Segment::new(map(&segment.text), segment.offset))
+        .collect()
+}
+
+/// Lowercases or case folds every segment.
+pub struct ChangeCase(pub CaseMode);
+
+impl Normalizer for ChangeCase {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        // some rules depend on the letters around, as in a final sigma
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<String>();
+
+        let mut index = 0;
+        segments
+            .into_iter()
+            .map(|segment| {
+                let changed = change_case(&text, index, &segment.text, self.0);
+                index += segment.text.len();
+                Segment::new(changed, segment.offset)
+            })
+            .collect()
+    }
+}
+
+/// Folds full-width Latin to ASCII and half-width katakana to full-width.
+pub struct FoldWidth;
+
+impl Normalizer for FoldWidth {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        map_text(segments, fold_width)
+    }
+}
+
+/// Folds katakana to hiragana.
+pub struct FoldKana;
+
+impl Normalizer for FoldKana {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        map_text(segments, |text| text.chars().map(fold_kana).collect())
+    }
+}
+
+/// Folds Traditional Chinese characters to Simplified ones.
+pub struct FoldChineseVariants;
+
+impl Normalizer for FoldChineseVariants {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        map_text(segments, |text| {
+            text.chars().map(fold_chinese_variant).collect()
+        })
+    }
+}
+
+/// Removes accents, and the strokes of letters such as `ø` and `ł`.
+pub struct StripDiacritics;
+
+impl Normalizer for StripDiacritics {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        map_text(segments, strip_diacritics)
+    }
+}
+
+/// Maps every segment to its TR39 skeleton, changing the case of the result
+/// again when given a case mode, as some prototypes are uppercase.
+pub struct FoldConfusables(pub Option<CaseMode>);
+
+impl Normalizer for FoldConfusables {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        map_text(segments, |text| {
+            let skeleton = skeleton(text);
+            match self.0 {
+                Some(mode) => change_case(&skeleton, 0, &skeleton, mode),
+                None => skeleton,
+            }
+        })
+    }
+}
+
+/// Splits segments that earlier stages expanded into several graphemes, such
+/// as `ss` from `ß`, and composes each grapheme.
+pub struct SplitGraphemes;
+
+impl Normalizer for SplitGraphemes {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        segments
+            .into_iter()
+            .flat_map(|segment| {
+                segment
+                    .text
+                    .graphemes(true)
+                    .map(|grapheme| {
+                        Segment::new(grapheme.nfc().collect::<String>(), segment.offset)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Splits every segment into its compatibility decomposition, one character
+/// per segment, so that accents are scored on their own.
+pub struct Decompose;
+
+impl Normalizer for Decompose {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        segments
+            .into_iter()
+            .flat_map(|segment| {
+                segment
+                    .text
+                    .nfkd()
+                    .map(|char| Segment::new(char, segment.offset))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Joins every number into a single segment of ASCII digits, with its decimal
+/// part, so that it is scored as a whole.
+pub struct JoinNumbers;
+
+impl Normalizer for JoinNumbers {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        let digit = |segment: &Segment| {
+            let mut chars = segment.text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(char), None) => digit_value(char),
+                _ => None,
+            }
+        };
+
+        let mut joined: Vec<Segment> = vec![];
+        let mut in_number = false;
+
+        for (i, segment) in segments.iter().enumerate() {
+            if let Some(value) = digit(segment) {
+                let value = char::from_digit(value, 10).unwrap_or('0');
+                match joined.last_mut() {
+                    Some(last) if in_number => last.text.push(value),
+                    _ => joined.push(Segment::new(value, segment.offset)),
+                }
+                in_number = true;
+                continue;
+            }
+
+            let decimal_point = in_number
+                && segment.text == "."
+                && joined.last().is_some_and(|last| !last.text.contains('.'))
+                && segments.get(i + 1).and_then(digit).is_some();
+            if decimal_point {
+                if let Some(last) = joined.last_mut() {
+                    last.text.push('.');
+                }
+            } else {
+                joined.push(segment.clone());
+                in_number = false;
+            }
+        }
+
+        joined
+    }
+}
+
+/// Replaces every run of whitespace with a single space, and removes it from
+/// both ends.
+pub struct CollapseWhitespace;
+
+impl Normalizer for CollapseWhitespace {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        let mut collapsed: Vec<Segment> = vec![];
+        let mut last_whitespace = true;
+
+        for segment in segments {
+            if WHITESPACE_REGEX.is_match(&segment.text) {
+                if !last_whitespace {
+                    collapsed.push(Segment::new(" ", segment.offset));
+                    last_whitespace = true;
+                }
+            } else {
+                collapsed.push(segment);
+                last_whitespace = false;
+            }
+        }
+
+        if last_whitespace {
+            collapsed.pop_if(|segment| segment.text == " ");
+        }
+
+        collapsed
+    }
+}
+
+/// Removes punctuation and other ASCII symbols.
+pub struct RemoveSymbols;
+
+impl Normalizer for RemoveSymbols {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        segments
+            .into_iter()
+            .filter(|segment| !NON_WORD_REGEX.is_match(&segment.text))
+            .collect()
+    }
+}
+
+// Keeps the whitespace-separated tokens for which `keep` holds, along with the
+// whitespace after them. Everything is kept when no token would be, so that a
+// query of only stop words can still match.
+fn retain_tokens(segments: Vec<Segment>, keep: impl Fn(&[Segment]) -> bool) -> Vec<Segment> {
+    let mut leading = vec![];
+    // each token, with the whitespace after it
+    let mut tokens: Vec<(Vec<Segment>, Vec<Segment>)> = vec![];
+
+    for segment in segments {
+        let whitespace = WHITESPACE_REGEX.is_match(&segment.text);
+        match tokens.last_mut() {
+            Some((_, after)) if whitespace => after.push(segment),
+            Some((token, after)) if after.is_empty() => token.push(segment),
+            _ if whitespace => leading.push(segment),
+            _ => tokens.push((vec![segment], vec![])),
+        }
+    }
+
+    let kept = tokens
+        .iter()
+        .map(|(token, _)| keep(token))
+        .collect::<Vec<_>>();
+    if !kept.contains(&true) {
+        return leading
+            .into_iter()
+            .chain(
+                tokens
+                    .into_iter()
+                    .flat_map(|(token, after)| token.into_iter().chain(after)),
+            )
+            .collect();
+    }
+
+    let mut retained = leading;
+    for ((token, after), kept) in tokens.into_iter().zip(&kept) {
+        if *kept {
+            retained.extend(token);
+            retained.extend(after);
+        }
+    }
+
+    // the whitespace that separated the last kept token from dropped ones
+    if kept.last() == Some(&false) {
+        while retained
+            .last()
+            .is_some_and(|segment| WHITESPACE_REGEX.is_match(&segment.text))
+        {
+            retained.pop();
+        }
+    }
+
+    retained
+}
+
+/// Removes the tokens that are stop words, such as `the` in `the lord of the
+/// rings`.
+pub struct RemoveStopWords(pub Arc<StopWords>);
+
+impl Normalizer for RemoveStopWords {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        retain_tokens(segments, |token| {
+            let word = token
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<String>();
+            !self.0.contains(&word)
+        })
+    }
+}
+
+/// Removes the tokens with fewer graphemes than the given length.
+pub struct MinTokenLength(pub usize);
+
+impl Normalizer for MinTokenLength {
+    fn normalize(&self, segments: Vec<Segment>) -> Vec<Segment> {
+        retain_tokens(segments, |token| token.len() >= self.0)
+    }
+}
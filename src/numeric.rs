@@ -0,0 +1,85 @@
+use unicode_general_category::{get_general_category, GeneralCategory};
+
+use crate::NumericMatch;
+
+/// The value of a decimal digit in any script, such as `٣` or `３`.
+pub(crate) fn digit_value(char: char) -> Option<u32> {
+    if let Some(digit) = char.to_digit(10) {
+        return Some(digit);
+    }
+    if get_general_category(char) != GeneralCategory::DecimalNumber {
+        return None;
+    }
+
+    // digits come in runs from zero to nine, some of them back to back
+    let mut zero = char as u32;
+    while char::from_u32(zero - 1)
+        .is_some_and(|char| get_general_category(char) == GeneralCategory::DecimalNumber)
+    {
+        zero -= 1;
+    }
+
+    Some((char as u32 - zero) % 10)
+}
+
+/// Every number in `text`, as ASCII digits with an optional decimal part.
+pub(crate) fn numbers(text: &str) -> Vec<String> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut numbers = vec![];
+    let mut number = String::new();
+
+    for (i, char) in chars.iter().enumerate() {
+        if let Some(digit) = digit_value(*char) {
+            number.push(char::from_digit(digit, 10).unwrap_or('0'));
+            continue;
+        }
+
+        let decimal_point = *char == '.'
+            && !number.is_empty()
+            && !number.contains('.')
+            && chars.get(i + 1).copied().and_then(digit_value).is_some();
+        if decimal_point {
+            number.push('.');
+        } else if !number.is_empty() {
+            numbers.push(std::mem::take(&mut number));
+        }
+    }
+
+    if !number.is_empty() {
+        numbers.push(number);
+    }
+
+    numbers
+}
+
+/// Whether `text` is a whole number unit, as built by
+/// [`JoinNumbers`](crate::JoinNumbers).
+pub(crate) fn is_number(text: &str) -> bool {
+    text.starts_with(|char: char| char.is_ascii_digit())
+        && text
+            .chars()
+            .all(|char| char.is_ascii_digit() || char == '.')
+}
+
+/// Whether a number of the term matches a number of the key.
+pub(crate) fn agree(term: &str, key: &str, mode: NumericMatch) -> bool {
+    let value = |number: &str| number.parse::<f64>().ok();
+
+    match mode {
+        NumericMatch::Exact => value(term).is_some() && value(term) == value(key),
+        NumericMatch::Prefix => key.starts_with(term),
+        NumericMatch::Tolerance(tolerance) => match (value(term), value(key)) {
+            (Some(term), Some(key)) => (term - key).abs() <= tolerance,
+            _ => false,
+        },
+    }
+}
+
+/// Whether every number of the term matches a number of the key.
+pub(crate) fn all_agree(term: &str, key: &str, mode: NumericMatch) -> bool {
+    let key_numbers = numbers(key);
+
+    numbers(term)
+        .iter()
+        .all(|term| key_numbers.iter().any(|key| agree(term, key, mode)))
+}
@@ -1,96 +1,393 @@
-use std::marker::PhantomData;
+use std::{cmp::Ordering, fmt, marker::PhantomData, sync::Arc};
 
-#[derive(Copy, Clone, Debug)]
-pub enum SortKind {
+use crate::{EditCosts, MatchData, Pipeline, StopWords};
+
+pub type SortComparator<T> = Box<dyn Fn(&MatchData<T>, &MatchData<T>) -> Ordering + Send + Sync>;
+
+pub enum SortKind<T> {
     InsertOrder,
+    /// By the tie-break chain, which starts with the score unless reordered.
     BestMatch,
+    /// Alphabetically by the matched key once normalized, then by the
+    /// tie-break chain.
+    ByKey,
+    /// By a comparator of the caller's, falling back to the tie-break chain
+    /// for matches it finds equal.
+    Custom(SortComparator<T>),
+}
+
+impl<T> fmt::Debug for SortKind<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortKind::InsertOrder => write!(f, "InsertOrder"),
+            SortKind::BestMatch => write!(f, "BestMatch"),
+            SortKind::ByKey => write!(f, "ByKey"),
+            SortKind::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// A criterion that orders matches, in the chain that `BestMatch` sorts by.
+/// Each criterion only decides between matches that the ones before it find
+/// equal, and insertion order settles what is left.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TieBreak {
+    /// Higher scores first.
+    Score,
+    /// Matches that start earlier in the key first.
+    MatchIndex,
+    /// Matches on an earlier key of the item first, for items with several
+    /// keys.
+    KeyIndex,
+    /// Keys whose length is closer to the term's first.
+    KeyLength,
+    /// Items added earlier first.
+    InsertOrder,
+}
+
+impl TieBreak {
+    /// The chain used unless one is given.
+    pub const DEFAULT: [TieBreak; 5] = [
+        TieBreak::Score,
+        TieBreak::MatchIndex,
+        TieBreak::KeyIndex,
+        TieBreak::KeyLength,
+        TieBreak::InsertOrder,
+    ];
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MatchKind {
+    Fuzzy,
+    /// Also matches the term as a subsequence of the key's words, for code
+    /// identifiers such as `get_config_file` or `HttpRequestContext`.
+    Identifier,
+    /// Matches the term against a path segment by segment, preferring matches
+    /// on the file name. A `**` segment in the term skips any number of
+    /// directories.
+    Path,
+}
+
+/// An encoder whose codes are indexed alongside each key, so that names that
+/// sound alike match. See the [`phonetic`](crate::phonetic) module.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PhoneticAlgorithm {
+    Soundex,
+    Metaphone,
+    DoubleMetaphone,
+    /// The Cologne phonetics, for German names.
+    Cologne,
+}
+
+/// How a number in the term matches a number in a key, when
+/// `numeric_tokens` is set. Numbers that don't match rule the key out.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NumericMatch {
+    /// The numbers have the same value, so `1.50` matches `1.5`.
+    Exact,
+    /// The key's number starts with the term's digits, so `13` matches
+    /// `1350`.
+    Prefix,
+    /// The numbers are at most this far apart.
+    Tolerance(f64),
+}
+
+/// How case is ignored when `ignore_case` is set.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CaseMode {
+    /// Plain lowercasing, as `str::to_lowercase` does.
+    Lower,
+    /// Full Unicode case folding, so that `ß` matches `ss` and `ς` matches
+    /// `σ`.
+    Fold,
+    /// Full case folding with the tailoring for a locale whose casing differs
+    /// from the default.
+    FoldLocale(Locale),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Locale {
+    /// Dotted `İ` folds to `i` and dotless `I` to `ı`.
+    Turkish,
+    /// Cased the same way as Turkish.
+    Azeri,
+    /// Keeps the dot above `i` and `j` when they carry another accent.
+    Lithuanian,
 }
 
+/// How a boost combines with the score of a match.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BoostMode {
+    /// The score is multiplied by the boost, so `1.0` leaves it as it is.
+    Multiply,
+    /// The boost is added to the score, so `0.0` leaves it as it is.
+    Add,
+}
+
+pub type Boost<T> = Box<dyn Fn(&T) -> f64 + Send + Sync>;
+
+pub type KeySelector<T> = Box<dyn Fn(&T) -> Vec<String> + Send + Sync>;
+
 pub struct A;
 pub struct DefaultVec;
 
-pub struct FuzzyOptions<'a, T, U = A> {
+pub struct FuzzyOptions<T, U = A> {
     pub(crate) ignore_case: bool,
+    pub(crate) case_mode: CaseMode,
     pub(crate) ignore_symbols: bool,
+    pub(crate) strip_diacritics: bool,
+    pub(crate) fold_width: bool,
+    pub(crate) fold_kana: bool,
+    pub(crate) fold_chinese_variants: bool,
+    pub(crate) transliterate: bool,
+    pub(crate) fold_confusables: bool,
+    pub(crate) phonetic: Option<PhoneticAlgorithm>,
+    pub(crate) phonetic_weight: f64,
+    pub(crate) pipeline: Option<Arc<Pipeline>>,
+    pub(crate) stop_words: Option<Arc<StopWords>>,
+    pub(crate) min_token_length: usize,
+    pub(crate) numeric_tokens: Option<NumericMatch>,
     pub(crate) normalize_whitespace: bool,
     pub(crate) use_damerau: bool,
     pub(crate) use_sellers: bool,
     pub(crate) use_separated_unicode: bool,
-    pub(crate) sort_by: SortKind,
-    pub(crate) key_selector: Box<dyn Fn(&'a T) -> Vec<String>>,
+    pub(crate) sort_by: SortKind<T>,
+    pub(crate) tie_breaks: Vec<TieBreak>,
+    pub(crate) match_kind: MatchKind,
+    pub(crate) key_selector: KeySelector<T>,
+    pub(crate) boost: Option<Boost<T>>,
+    pub(crate) boost_mode: BoostMode,
     pub(crate) threshold: f64,
+    pub(crate) edit_costs: EditCosts,
     _marker: PhantomData<U>,
 }
 
-impl<'a, StringLike> Default for FuzzyOptions<'a, StringLike>
+impl<StringLike> Default for FuzzyOptions<StringLike>
 where
     StringLike: ToString,
 {
     fn default() -> Self {
         Self {
             ignore_case: true,
+            case_mode: CaseMode::Lower,
             ignore_symbols: true,
+            strip_diacritics: false,
+            fold_width: false,
+            fold_kana: false,
+            fold_chinese_variants: false,
+            transliterate: false,
+            fold_confusables: false,
+            phonetic: None,
+            phonetic_weight: 0.5,
+            pipeline: None,
+            stop_words: None,
+            min_token_length: 0,
+            numeric_tokens: None,
             normalize_whitespace: true,
             use_damerau: true,
             use_sellers: true,
             use_separated_unicode: false,
             sort_by: SortKind::BestMatch,
+            tie_breaks: TieBreak::DEFAULT.to_vec(),
+            match_kind: MatchKind::Fuzzy,
             key_selector: Box::new(|x| vec![x.to_string()]),
+            boost: None,
+            boost_mode: BoostMode::Multiply,
             threshold: 0.6,
+            edit_costs: EditCosts::default(),
             _marker: PhantomData,
         }
     }
 }
 
-impl<'a, StringLike> Default for FuzzyOptions<'a, Vec<StringLike>, DefaultVec>
+impl<StringLike> Default for FuzzyOptions<Vec<StringLike>, DefaultVec>
 where
     StringLike: ToString,
 {
     fn default() -> Self {
         Self {
             ignore_case: true,
+            case_mode: CaseMode::Lower,
             ignore_symbols: true,
+            strip_diacritics: false,
+            fold_width: false,
+            fold_kana: false,
+            fold_chinese_variants: false,
+            transliterate: false,
+            fold_confusables: false,
+            phonetic: None,
+            phonetic_weight: 0.5,
+            pipeline: None,
+            stop_words: None,
+            min_token_length: 0,
+            numeric_tokens: None,
             normalize_whitespace: true,
             use_damerau: true,
             use_sellers: true,
             use_separated_unicode: false,
             sort_by: SortKind::BestMatch,
+            tie_breaks: TieBreak::DEFAULT.to_vec(),
+            match_kind: MatchKind::Fuzzy,
             key_selector: Box::new(|x| x.iter().map(|x| x.to_string()).collect()),
+            boost: None,
+            boost_mode: BoostMode::Multiply,
             threshold: 0.6,
+            edit_costs: EditCosts::default(),
             _marker: PhantomData,
         }
     }
 }
 
-impl<'a, T> FuzzyOptions<'a, T> {
+impl<T> FuzzyOptions<T> {
     pub fn default_with_key_selector<Func>(key_selector: Func) -> Self
     where
-        Func: Fn(&'a T) -> Vec<String> + 'static,
+        Func: Fn(&T) -> Vec<String> + Send + Sync + 'static,
     {
         Self {
             key_selector: Box::new(key_selector),
             ignore_case: true,
+            case_mode: CaseMode::Lower,
             ignore_symbols: true,
+            strip_diacritics: false,
+            fold_width: false,
+            fold_kana: false,
+            fold_chinese_variants: false,
+            transliterate: false,
+            fold_confusables: false,
+            phonetic: None,
+            phonetic_weight: 0.5,
+            pipeline: None,
+            stop_words: None,
+            min_token_length: 0,
+            numeric_tokens: None,
             normalize_whitespace: true,
             use_damerau: true,
             use_sellers: true,
             use_separated_unicode: false,
             sort_by: SortKind::BestMatch,
+            tie_breaks: TieBreak::DEFAULT.to_vec(),
+            match_kind: MatchKind::Fuzzy,
+            boost: None,
+            boost_mode: BoostMode::Multiply,
             threshold: 0.6,
+            edit_costs: EditCosts::default(),
             _marker: PhantomData,
         }
     }
+}
 
+impl<T, U> FuzzyOptions<T, U> {
     pub fn ignore_case(mut self, ignore_case: bool) -> Self {
         self.ignore_case = ignore_case;
         self
     }
 
+    pub fn case_mode(mut self, case_mode: CaseMode) -> Self {
+        self.case_mode = case_mode;
+        self
+    }
+
     pub fn ignore_symbols(mut self, ignore_symbols: bool) -> Self {
         self.ignore_symbols = ignore_symbols;
         self
     }
 
+    /// Whether to match letters regardless of their accents, so that `cafe`
+    /// matches `café`. Unlike `use_separated_unicode`, graphemes are still
+    /// scored whole.
+    pub fn strip_diacritics(mut self, strip_diacritics: bool) -> Self {
+        self.strip_diacritics = strip_diacritics;
+        self
+    }
+
+    /// Whether full-width Latin matches ASCII and half-width katakana matches
+    /// full-width, without the other compatibility folds of NFKC.
+    pub fn fold_width(mut self, fold_width: bool) -> Self {
+        self.fold_width = fold_width;
+        self
+    }
+
+    /// Whether hiragana and katakana with the same sound match each other.
+    pub fn fold_kana(mut self, fold_kana: bool) -> Self {
+        self.fold_kana = fold_kana;
+        self
+    }
+
+    /// Whether Traditional and Simplified Chinese characters match each
+    /// other.
+    pub fn fold_chinese_variants(mut self, fold_chinese_variants: bool) -> Self {
+        self.fold_chinese_variants = fold_chinese_variants;
+        self
+    }
+
+    /// Whether keys with Chinese characters or kana can also be found by
+    /// their romanization: pinyin with or without tones, pinyin initials such
+    /// as `bj` for 北京, and Hepburn romaji for kana and for the kanji of
+    /// common Japanese place names and words, such as `tokyo` for 東京.
+    /// Matches are still reported against the original key.
+    pub fn transliterate(mut self, transliterate: bool) -> Self {
+        self.transliterate = transliterate;
+        self
+    }
+
+    /// Whether characters that look alike match each other, by comparing
+    /// their Unicode TR39 skeletons. This makes `pаypal`, with a Cyrillic `а`,
+    /// match `paypal`.
+    pub fn fold_confusables(mut self, fold_confusables: bool) -> Self {
+        self.fold_confusables = fold_confusables;
+        self
+    }
+
+    /// Indexes the phonetic codes of every key, so that `Catherine` matches
+    /// `Kathryn`. Scores then blend in how many of the term's words sound like
+    /// one of the key's words, by `phonetic_weight`.
+    pub fn phonetic(mut self, phonetic: PhoneticAlgorithm) -> Self {
+        self.phonetic = Some(phonetic);
+        self
+    }
+
+    /// How much phonetic agreement counts towards the score, between 0 and 1.
+    pub fn phonetic_weight(mut self, phonetic_weight: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&phonetic_weight),
+            "phonetic weight must be between 0 and 1, got {phonetic_weight}"
+        );
+        self.phonetic_weight = phonetic_weight;
+        self
+    }
+
+    /// Normalizes keys and terms with `pipeline` instead of the stages the
+    /// other options ask for, which it then ignores. Start from
+    /// [`Pipeline::from_options`] to add stages to the default ones.
+    pub fn pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = Some(Arc::new(pipeline));
+        self
+    }
+
+    /// Removes stop words from keys and terms before they are scored, so
+    /// that `lord rings` matches `The Lord of the Rings` closely. Matches are
+    /// still reported against the key as written.
+    pub fn stop_words(mut self, stop_words: StopWords) -> Self {
+        self.stop_words = Some(Arc::new(stop_words));
+        self
+    }
+
+    /// Removes words with fewer graphemes than `min_token_length` from keys
+    /// and terms before they are scored.
+    pub fn min_token_length(mut self, min_token_length: usize) -> Self {
+        self.min_token_length = min_token_length;
+        self
+    }
+
+    /// Compares runs of digits, including decimals and digits of other
+    /// scripts, as whole numbers rather than grapheme by grapheme, so that
+    /// `iphone 13` does not match `iphone 12`. A key only matches when every
+    /// number of the term matches one of its numbers.
+    pub fn numeric_tokens(mut self, numeric_tokens: NumericMatch) -> Self {
+        self.numeric_tokens = Some(numeric_tokens);
+        self
+    }
+
     pub fn normalize_whitespace(mut self, normalize_whitespace: bool) -> Self {
         self.normalize_whitespace = normalize_whitespace;
         self
@@ -111,35 +408,131 @@ impl<'a, T> FuzzyOptions<'a, T> {
         self
     }
 
-    pub fn sort_by(mut self, sort_by: SortKind) -> Self {
+    pub fn sort_by(mut self, sort_by: SortKind<T>) -> Self {
         self.sort_by = sort_by;
         self
     }
 
+    /// Replaces the chain of criteria that matches are ordered by, which is
+    /// [`TieBreak::DEFAULT`] unless set.
+    pub fn tie_breaks(mut self, tie_breaks: impl IntoIterator<Item = TieBreak>) -> Self {
+        self.tie_breaks = tie_breaks.into_iter().collect();
+        self
+    }
+
+    pub fn match_kind(mut self, match_kind: MatchKind) -> Self {
+        self.match_kind = match_kind;
+        self
+    }
+
     pub fn key_selector<F>(mut self, key_selector: F) -> Self
     where
-        F: Fn(&'a T) -> Vec<String> + 'static,
+        F: Fn(&T) -> Vec<String> + Send + Sync + 'static,
     {
         self.key_selector = Box::new(key_selector);
         self
     }
 
+    /// Weighs the score of every match by a prior of its item, such as its
+    /// popularity, once matches are found. Items are still matched by their
+    /// text against `threshold`, so a boost can't bring in an item that
+    /// doesn't match. Applies to searches, but not to `fuzzy`.
+    pub fn boost<F>(mut self, boost: F) -> Self
+    where
+        F: Fn(&T) -> f64 + Send + Sync + 'static,
+    {
+        self.boost = Some(Box::new(boost));
+        self
+    }
+
+    pub fn boost_mode(mut self, boost_mode: BoostMode) -> Self {
+        self.boost_mode = boost_mode;
+        self
+    }
+
     pub fn threshold(mut self, threshold: f64) -> Self {
         self.threshold = threshold;
         self
     }
 
-    pub fn as_str_options(&self) -> FuzzyOptions<'a, String> {
+    pub fn edit_costs(mut self, edit_costs: EditCosts) -> Self {
+        self.edit_costs = edit_costs;
+        self
+    }
+
+    /// The keys `item` is matched by, as the key selector returns them.
+    pub fn keys(&self, item: &T) -> Vec<String> {
+        (self.key_selector)(item)
+    }
+
+    pub fn as_str_options(&self) -> FuzzyOptions<String> {
         FuzzyOptions {
             key_selector: Box::new(|x| vec![x.clone()]),
             ignore_case: self.ignore_case,
+            case_mode: self.case_mode,
+            ignore_symbols: self.ignore_symbols,
+            strip_diacritics: self.strip_diacritics,
+            fold_width: self.fold_width,
+            fold_kana: self.fold_kana,
+            fold_chinese_variants: self.fold_chinese_variants,
+            transliterate: self.transliterate,
+            fold_confusables: self.fold_confusables,
+            phonetic: self.phonetic,
+            phonetic_weight: self.phonetic_weight,
+            pipeline: self.pipeline.clone(),
+            stop_words: self.stop_words.clone(),
+            min_token_length: self.min_token_length,
+            numeric_tokens: self.numeric_tokens,
+            normalize_whitespace: self.normalize_whitespace,
+            use_damerau: self.use_damerau,
+            use_sellers: self.use_sellers,
+            use_separated_unicode: self.use_separated_unicode,
+            // a custom comparator only knows how to compare the original items
+            sort_by: match self.sort_by {
+                SortKind::InsertOrder => SortKind::InsertOrder,
+                SortKind::BestMatch | SortKind::Custom(_) => SortKind::BestMatch,
+                SortKind::ByKey => SortKind::ByKey,
+            },
+            tie_breaks: self.tie_breaks.clone(),
+            match_kind: self.match_kind,
+            // a boost only knows how to weigh the original items
+            boost: None,
+            boost_mode: self.boost_mode,
+            threshold: self.threshold,
+            edit_costs: self.edit_costs.clone(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn erase_marker(self) -> FuzzyOptions<T> {
+        FuzzyOptions {
+            key_selector: self.key_selector,
+            ignore_case: self.ignore_case,
+            case_mode: self.case_mode,
             ignore_symbols: self.ignore_symbols,
+            strip_diacritics: self.strip_diacritics,
+            fold_width: self.fold_width,
+            fold_kana: self.fold_kana,
+            fold_chinese_variants: self.fold_chinese_variants,
+            transliterate: self.transliterate,
+            fold_confusables: self.fold_confusables,
+            phonetic: self.phonetic,
+            phonetic_weight: self.phonetic_weight,
+            pipeline: self.pipeline,
+            stop_words: self.stop_words,
+            min_token_length: self.min_token_length,
+            numeric_tokens: self.numeric_tokens,
             normalize_whitespace: self.normalize_whitespace,
             use_damerau: self.use_damerau,
             use_sellers: self.use_sellers,
             use_separated_unicode: self.use_separated_unicode,
             sort_by: self.sort_by,
+            tie_breaks: self.tie_breaks,
+            match_kind: self.match_kind,
+            boost: self.boost,
+            boost_mode: self.boost_mode,
             threshold: self.threshold,
+            edit_costs: self.edit_costs,
             _marker: PhantomData,
         }
     }
@@ -0,0 +1,172 @@
+use crate::{score::Scorer, util::normalize, FuzzyOptions};
+
+// how much a match loses when the last term segment isn't on the file name
+const DIRECTORY_FACTOR: f64 = 0.85;
+// how much a match loses for every key segment the term didn't match
+const DEPTH_PENALTY: f64 = 0.02;
+const MIN_DEPTH_FACTOR: f64 = 0.8;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PathMatch {
+    pub(crate) score: f64,
+    // byte ranges of the key, as (index, length)
+    pub(crate) spans: Vec<(usize, usize)>,
+}
+
+struct TermSegment {
+    normal: Vec<String>,
+    // preceded by `**`, so any number of key segments may be skipped before it
+    skip: bool,
+}
+
+struct SegmentMatch {
+    score: f64,
+    span: Option<(usize, usize)>,
+}
+
+/// Splits a path into its `/` or `\` separated segments, as byte ranges.
+pub(crate) fn split_segments(path: &str) -> Vec<(usize, usize)> {
+    let mut segments = vec![];
+    let mut start = 0;
+
+    for (index, c) in path.char_indices().chain([(path.len(), '/')]) {
+        if c == '/' || c == '\\' {
+            if index > start {
+                segments.push((start, index - start));
+            }
+            start = index + c.len_utf8();
+        }
+    }
+
+    segments
+}
+
+fn parse_term<T, U>(term: &str, options: &FuzzyOptions<T, U>) -> Vec<TermSegment> {
+    let mut segments = vec![];
+    let mut skip = false;
+
+    for (index, length) in split_segments(term) {
+        let segment = &term[index..index + length];
+        if segment == "**" {
+            skip = true;
+            continue;
+        }
+
+        segments.push(TermSegment {
+            normal: normalize(segment.to_string(), options).normal,
+            skip,
+        });
+        skip = false;
+    }
+
+    segments
+}
+
+/// Scores `term` against `key` segment by segment, so that `src/ut` matches
+/// `src/util.rs`. Consecutive term segments have to match consecutive key
+/// segments, unless they are separated by `**`. The first term segment may
+/// match at any depth.
+///
+/// Every term segment has to reach the threshold on its own. The result is
+/// their average, lowered when the last segment doesn't land on the file
+/// name, and for every key segment the term didn't account for.
+pub(crate) fn score_path<T, U>(
+    term: &str,
+    key: &str,
+    options: &FuzzyOptions<T, U>,
+) -> Option<PathMatch> {
+    let term = parse_term(term, options);
+    let key_segments = split_segments(key);
+    if term.is_empty() {
+        return Some(PathMatch {
+            score: 1.0,
+            spans: vec![],
+        });
+    }
+
+    let scorer = Scorer::new(options);
+    let matches = term
+        .iter()
+        .map(|segment| {
+            key_segments
+                .iter()
+                .map(|&(index, length)| {
+                    let normalized = normalize(key[index..index + length].to_string(), options);
+                    let (score, start, length) = scorer.score(&segment.normal, &normalized);
+                    let (start, length) = normalized.denormalize(start, length);
+
+                    SegmentMatch {
+                        score,
+                        span: (length > 0).then_some((index + start, length)),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let weights = term
+        .iter()
+        .map(|segment| segment.normal.len().max(1) as f64)
+        .collect::<Vec<_>>();
+
+    // best[a][b] is the best weighted total for term[..=a] with term[a] on
+    // key segment b, and from[a][b] the key segment term[a - 1] was on
+    let mut best = vec![vec![None::<f64>; key_segments.len()]; term.len()];
+    let mut from = vec![vec![0; key_segments.len()]; term.len()];
+
+    for (a, segment) in term.iter().enumerate() {
+        for b in 0..key_segments.len() {
+            let current = &matches[a][b];
+            if current.score < options.threshold {
+                continue;
+            }
+
+            let own = current.score * weights[a];
+            if a == 0 {
+                best[a][b] = Some(own);
+                continue;
+            }
+
+            let previous = if segment.skip {
+                0..b
+            } else {
+                b.saturating_sub(1)..b
+            };
+            if let Some((total, previous)) = previous
+                .filter_map(|previous| Some((best[a - 1][previous]?, previous)))
+                .max_by(|x, y| x.0.total_cmp(&y.0))
+            {
+                best[a][b] = Some(total + own);
+                from[a][b] = previous;
+            }
+        }
+    }
+
+    let last = term.len() - 1;
+    let total_weight = weights.iter().sum::<f64>();
+    let (mut b, score) = best[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(b, total)| {
+            let mut score = (*total)? / total_weight;
+            if b != key_segments.len() - 1 {
+                score *= DIRECTORY_FACTOR;
+            }
+
+            let unmatched = key_segments.len() - term.len();
+            score *= (1.0 - DEPTH_PENALTY * unmatched as f64).max(MIN_DEPTH_FACTOR);
+            Some((b, score))
+        })
+        .max_by(|x, y| x.1.total_cmp(&y.1))?;
+
+    let mut spans = vec![];
+    for a in (0..term.len()).rev() {
+        spans.extend(matches[a][b].span);
+        if a > 0 {
+            b = from[a][b];
+        }
+    }
+    spans.reverse();
+
+    Some(PathMatch { score, spans })
+}
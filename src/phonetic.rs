@@ -0,0 +1,925 @@
+//! Phonetic encoders, which give words that sound alike the same code.
+//!
+//! All of them are designed for names written in the Latin alphabet, and
+//! ignore anything else.
+
+use crate::{fold::strip_diacritics, PhoneticAlgorithm};
+
+// the length of the codes produced by the metaphone encoders
+const METAPHONE_LENGTH: usize = 4;
+
+/// Encodes `word` with American Soundex, so that `Robert` and `Rupert` both
+/// become `R163`. Returns an empty code for words without Latin letters.
+pub fn soundex(word: &str) -> String {
+    let letters = letters(word);
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+
+    let digit = |letter: u8| match letter {
+        b'B' | b'F' | b'P' | b'V' => b'1',
+        b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => b'2',
+        b'D' | b'T' => b'3',
+        b'L' => b'4',
+        b'M' | b'N' => b'5',
+        b'R' => b'6',
+        _ => b'0',
+    };
+
+    let mut code = vec![first];
+    let mut last = digit(first);
+
+    for &letter in &letters[1..] {
+        // unlike vowels, `H` and `W` don't separate letters with the same code
+        if letter == b'H' || letter == b'W' {
+            continue;
+        }
+
+        let current = digit(letter);
+        if current != b'0' && current != last {
+            code.push(current);
+        }
+        last = current;
+    }
+
+    code.resize(4, b'0');
+    String::from_utf8(code).unwrap_or_default()
+}
+
+/// Encodes `word` with the original Metaphone, so that `Knight` becomes `NT`.
+/// `0` stands for `th`, and `X` for `sh`.
+pub fn metaphone(word: &str) -> String {
+    let word = letters(word);
+    let at = |index: usize| word.get(index).copied().unwrap_or(0);
+    let is_vowel = |letter: u8| b"AEIOU".contains(&letter);
+    let is_front = |letter: u8| b"EIY".contains(&letter);
+
+    let mut code = String::new();
+    let mut index = 0;
+
+    match (at(0), at(1)) {
+        (b'A', b'E') => {
+            code.push('E');
+            index = 2;
+        }
+        (b'G' | b'K' | b'P', b'N') | (b'W', b'R') => index = 1,
+        (b'X', _) => {
+            code.push('S');
+            index = 1;
+        }
+        (b'W', b'H') => {
+            code.push('W');
+            index = 2;
+        }
+        _ => {}
+    }
+
+    while index < word.len() {
+        let letter = at(index);
+        let previous = if index > 0 { at(index - 1) } else { 0 };
+        let next = at(index + 1);
+
+        // doubled letters sound once, except for `cc` as in `accent`
+        if letter == previous && letter != b'C' {
+            index += 1;
+            continue;
+        }
+
+        match letter {
+            b'A' | b'E' | b'I' | b'O' | b'U' => {
+                if index == 0 {
+                    code.push(letter as char);
+                }
+            }
+            b'B' => {
+                if !(previous == b'M' && index + 1 == word.len()) {
+                    code.push('B');
+                }
+            }
+            b'C' => {
+                if next == b'I' && at(index + 2) == b'A' {
+                    code.push('X');
+                } else if next == b'H' {
+                    code.push(if previous == b'S' { 'K' } else { 'X' });
+                    index += 1;
+                } else if is_front(next) {
+                    if previous != b'S' {
+                        code.push('S');
+                    }
+                } else {
+                    code.push('K');
+                }
+            }
+            b'D' => {
+                if next == b'G' && is_front(at(index + 2)) {
+                    code.push('J');
+                    index += 2;
+                } else {
+                    code.push('T');
+                }
+            }
+            b'G' => {
+                let silent = (next == b'H'
+                    && !(index + 2 >= word.len() || is_vowel(at(index + 2))))
+                    || (next == b'N' && (index + 2 == word.len() || &word[index + 1..] == b"NED"));
+
+                if !silent {
+                    code.push(if is_front(next) && previous != b'G' {
+                        'J'
+                    } else {
+                        'K'
+                    });
+                }
+            }
+            b'H' => {
+                let after_vowel = is_vowel(previous) && !is_vowel(next);
+                if !after_vowel && !b"CSPTG".contains(&previous) {
+                    code.push('H');
+                }
+            }
+            b'K' => {
+                if previous != b'C' {
+                    code.push('K');
+                }
+            }
+            b'P' => code.push(if next == b'H' { 'F' } else { 'P' }),
+            b'Q' => code.push('K'),
+            b'S' => {
+                if next == b'H' {
+                    code.push('X');
+                    index += 1;
+                } else if next == b'I' && matches!(at(index + 2), b'O' | b'A') {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+            b'T' => {
+                if next == b'I' && matches!(at(index + 2), b'O' | b'A') {
+                    code.push('X');
+                } else if next == b'H' {
+                    code.push('0');
+                    index += 1;
+                } else if !(next == b'C' && at(index + 2) == b'H') {
+                    code.push('T');
+                }
+            }
+            b'V' => code.push('F'),
+            b'W' | b'Y' => {
+                if is_vowel(next) {
+                    code.push(letter as char);
+                }
+            }
+            b'X' => code.push_str("KS"),
+            b'Z' => code.push('S'),
+            _ => code.push(letter as char),
+        }
+
+        index += 1;
+    }
+
+    code
+}
+
+/// Encodes `word` with Double Metaphone, returning its primary and alternate
+/// codes, so that `Schmidt` becomes `XMT` and `SMT`.
+pub fn double_metaphone(word: &str) -> (String, String) {
+    DoubleMetaphone::new(word).encode()
+}
+
+/// Encodes `word` with the Cologne phonetics, which are tuned for German, so
+/// that `Müller` and `Mueller` both become `657`.
+pub fn cologne(word: &str) -> String {
+    let word = word
+        .to_uppercase()
+        .replace('ß', "S")
+        .chars()
+        .map(|char| match char {
+            'Ä' | 'Ö' | 'Ü' => 'A',
+            _ => char,
+        })
+        .filter(char::is_ascii_uppercase)
+        .map(|char| char as u8)
+        .collect::<Vec<_>>();
+
+    let at = |index: usize| word.get(index).copied().unwrap_or(0);
+    let mut digits = vec![];
+
+    for (index, &letter) in word.iter().enumerate() {
+        let previous = if index > 0 { at(index - 1) } else { 0 };
+        let next = at(index + 1);
+
+        let digit: &[u8] = match letter {
+            b'A' | b'E' | b'I' | b'J' | b'O' | b'U' | b'Y' => b"0",
+            b'H' => b"",
+            b'B' => b"1",
+            b'P' => {
+                if next == b'H' {
+                    b"3"
+                } else {
+                    b"1"
+                }
+            }
+            b'D' | b'T' => {
+                if b"CSZ".contains(&next) {
+                    b"8"
+                } else {
+                    b"2"
+                }
+            }
+            b'F' | b'V' | b'W' => b"3",
+            b'G' | b'K' | b'Q' => b"4",
+            b'C' => {
+                let hard = if index == 0 {
+                    b"AHKLOQRUX".contains(&next)
+                } else {
+                    b"AHKOQUX".contains(&next) && !b"SZ".contains(&previous)
+                };
+                if hard {
+                    b"4"
+                } else {
+                    b"8"
+                }
+            }
+            b'X' => {
+                if b"CKQ".contains(&previous) {
+                    b"8"
+                } else {
+                    b"48"
+                }
+            }
+            b'L' => b"5",
+            b'M' | b'N' => b"6",
+            b'R' => b"7",
+            _ => b"8",
+        };
+
+        digits.extend_from_slice(digit);
+    }
+
+    digits.dedup();
+
+    digits
+        .iter()
+        .enumerate()
+        .filter(|&(index, &digit)| index == 0 || digit != b'0')
+        .map(|(_, &digit)| digit as char)
+        .collect()
+}
+
+/// The codes of every word in `text`, with both codes for Double Metaphone.
+/// Words without a code are left out.
+pub(crate) fn encode(text: &str, algorithm: PhoneticAlgorithm) -> Vec<Vec<String>> {
+    text.split(|char: char| !char.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(|word| match algorithm {
+            PhoneticAlgorithm::Soundex => vec![soundex(word)],
+            PhoneticAlgorithm::Metaphone => vec![metaphone(word)],
+            PhoneticAlgorithm::DoubleMetaphone => {
+                let (primary, alternate) = double_metaphone(word);
+                if primary == alternate {
+                    vec![primary]
+                } else {
+                    vec![primary, alternate]
+                }
+            }
+            PhoneticAlgorithm::Cologne => vec![cologne(word)],
+        })
+        .map(|codes| {
+            codes
+                .into_iter()
+                .filter(|code| !code.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|codes| !codes.is_empty())
+        .collect()
+}
+
+/// The share of the term's words that sound like one of the key's words.
+pub(crate) fn agreement(term: &[Vec<String>], key: &[Vec<String>]) -> f64 {
+    if term.is_empty() {
+        return 0.0;
+    }
+
+    let agreeing = term
+        .iter()
+        .filter(|codes| {
+            key.iter()
+                .any(|key_codes| codes.iter().any(|code| key_codes.contains(code)))
+        })
+        .count();
+
+    agreeing as f64 / term.len() as f64
+}
+
+// The ASCII letters of `word` in uppercase, with accents removed.
+fn letters(word: &str) -> Vec<u8> {
+    strip_diacritics(word)
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|letter| letter.to_ascii_uppercase())
+        .collect()
+}
+
+struct DoubleMetaphone {
+    word: Vec<char>,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl DoubleMetaphone {
+    fn new(word: &str) -> Self {
+        let word = word.trim().to_uppercase();
+        let slavo_germanic = word.contains('W')
+            || word.contains('K')
+            || word.contains("CZ")
+            || word.contains("WITZ");
+
+        Self {
+            word: word.chars().collect(),
+            slavo_germanic,
+            primary: String::new(),
+            alternate: String::new(),
+        }
+    }
+
+    fn at(&self, index: isize) -> char {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.word.get(index))
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    fn is_vowel_at(&self, index: isize) -> bool {
+        matches!(self.at(index), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    // whether the text at `start` is one of `options`, all of which have the
+    // same length
+    fn contains(&self, start: isize, options: &[&str]) -> bool {
+        let length = options[0].chars().count() as isize;
+        if start < 0 || start + length > self.word.len() as isize {
+            return false;
+        }
+
+        let text = self.word[start as usize..(start + length) as usize]
+            .iter()
+            .collect::<String>();
+        options.contains(&text.as_str())
+    }
+
+    fn last(&self) -> isize {
+        self.word.len() as isize - 1
+    }
+
+    fn add(&mut self, primary: &str, alternate: &str) {
+        for (code, part) in [
+            (&mut self.primary, primary),
+            (&mut self.alternate, alternate),
+        ] {
+            for char in part.chars() {
+                if code.len() < METAPHONE_LENGTH {
+                    code.push(char);
+                }
+            }
+        }
+    }
+
+    fn add_both(&mut self, code: &str) {
+        self.add(code, code);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= METAPHONE_LENGTH && self.alternate.len() >= METAPHONE_LENGTH
+    }
+
+    fn encode(mut self) -> (String, String) {
+        let mut index: isize = 0;
+        if self.contains(0, &["GN", "KN", "PN", "WR", "PS"]) {
+            index = 1;
+        }
+
+        while !self.is_complete() && index <= self.last() {
+            index = match self.at(index) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if index == 0 {
+                        self.add_both("A");
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.add_both("P");
+                    index + if self.at(index + 1) == 'B' { 2 } else { 1 }
+                }
+                'Ç' => {
+                    self.add_both("S");
+                    index + 1
+                }
+                'C' => self.c(index),
+                'D' => self.d(index),
+                'F' => {
+                    self.add_both("F");
+                    index + if self.at(index + 1) == 'F' { 2 } else { 1 }
+                }
+                'G' => self.g(index),
+                'H' => {
+                    if (index == 0 || self.is_vowel_at(index - 1)) && self.is_vowel_at(index + 1) {
+                        self.add_both("H");
+                        index + 2
+                    } else {
+                        index + 1
+                    }
+                }
+                'J' => self.j(index),
+                'K' => {
+                    self.add_both("K");
+                    index + if self.at(index + 1) == 'K' { 2 } else { 1 }
+                }
+                'L' => self.l(index),
+                'M' => {
+                    self.add_both("M");
+                    let umb = self.contains(index - 1, &["UMB"])
+                        && (index + 1 == self.last() || self.contains(index + 2, &["ER"]));
+                    index
+                        + if self.at(index + 1) == 'M' || umb {
+                            2
+                        } else {
+                            1
+                        }
+                }
+                'N' => {
+                    self.add_both("N");
+                    index + if self.at(index + 1) == 'N' { 2 } else { 1 }
+                }
+                'Ñ' => {
+                    self.add_both("N");
+                    index + 1
+                }
+                'P' => {
+                    if self.at(index + 1) == 'H' {
+                        self.add_both("F");
+                        index + 2
+                    } else {
+                        self.add_both("P");
+                        index
+                            + if matches!(self.at(index + 1), 'P' | 'B') {
+                                2
+                            } else {
+                                1
+                            }
+                    }
+                }
+                'Q' => {
+                    self.add_both("K");
+                    index + if self.at(index + 1) == 'Q' { 2 } else { 1 }
+                }
+                'R' => self.r(index),
+                'S' => self.s(index),
+                'T' => self.t(index),
+                'V' => {
+                    self.add_both("F");
+                    index + if self.at(index + 1) == 'V' { 2 } else { 1 }
+                }
+                'W' => self.w(index),
+                'X' => self.x(index),
+                'Z' => self.z(index),
+                _ => index + 1,
+            };
+        }
+
+        (self.primary, self.alternate)
+    }
+
+    fn c(&mut self, index: isize) -> isize {
+        if self.c_is_k(index) {
+            self.add_both("K");
+            index + 2
+        } else if index == 0 && self.contains(index, &["CAESAR"]) {
+            self.add_both("S");
+            index + 2
+        } else if self.contains(index, &["CH"]) {
+            self.ch(index)
+        } else if self.contains(index, &["CZ"]) && !self.contains(index - 2, &["WICZ"]) {
+            self.add("S", "X");
+            index + 2
+        } else if self.contains(index + 1, &["CIA"]) {
+            self.add_both("X");
+            index + 3
+        } else if self.contains(index, &["CC"]) && !(index == 1 && self.at(0) == 'M') {
+            self.cc(index)
+        } else if self.contains(index, &["CK", "CG", "CQ"]) {
+            self.add_both("K");
+            index + 2
+        } else if self.contains(index, &["CI", "CE", "CY"]) {
+            if self.contains(index, &["CIO", "CIE", "CIA"]) {
+                self.add("S", "X");
+            } else {
+                self.add_both("S");
+            }
+            index + 2
+        } else {
+            self.add_both("K");
+            if self.contains(index + 1, &[" C", " Q", " G"]) {
+                index + 3
+            } else if self.contains(index + 1, &["C", "K", "Q"])
+                && !self.contains(index + 1, &["CE", "CI"])
+            {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    // `ch` in words such as `bacchus` and `chianti`
+    fn c_is_k(&self, index: isize) -> bool {
+        if self.contains(index, &["CHIA"]) {
+            true
+        } else if index <= 1 || self.is_vowel_at(index - 2) || !self.contains(index - 1, &["ACH"]) {
+            false
+        } else {
+            let next = self.at(index + 2);
+            (next != 'I' && next != 'E') || self.contains(index - 2, &["BACHER", "MACHER"])
+        }
+    }
+
+    fn ch(&mut self, index: isize) -> isize {
+        if index > 0 && self.contains(index, &["CHAE"]) {
+            self.add("K", "X");
+        } else if self.ch_is_greek(index) || self.ch_is_germanic(index) {
+            self.add_both("K");
+        } else if index > 0 {
+            if self.contains(0, &["MC"]) {
+                self.add_both("K");
+            } else {
+                self.add("X", "K");
+            }
+        } else {
+            self.add_both("X");
+        }
+        index + 2
+    }
+
+    fn ch_is_greek(&self, index: isize) -> bool {
+        index == 0
+            && (self.contains(index + 1, &["HARAC", "HARIS"])
+                || self.contains(index + 1, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, &["CHORE"])
+    }
+
+    fn ch_is_germanic(&self, index: isize) -> bool {
+        self.contains(0, &["VAN ", "VON "])
+            || self.contains(0, &["SCH"])
+            || self.contains(index - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(index + 2, &["T", "S"])
+            || ((self.contains(index - 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.contains(
+                    index + 2,
+                    &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                ) || index + 1 == self.last()))
+    }
+
+    fn cc(&mut self, index: isize) -> isize {
+        if self.contains(index + 2, &["I", "E", "H"]) && !self.contains(index + 2, &["HU"]) {
+            if (index == 1 && self.at(index - 1) == 'A')
+                || self.contains(index - 1, &["UCCEE", "UCCES"])
+            {
+                self.add_both("KS");
+            } else {
+                self.add_both("X");
+            }
+            index + 3
+        } else {
+            self.add_both("K");
+            index + 2
+        }
+    }
+
+    fn d(&mut self, index: isize) -> isize {
+        if self.contains(index, &["DG"]) {
+            if self.contains(index + 2, &["I", "E", "Y"]) {
+                self.add_both("J");
+                index + 3
+            } else {
+                self.add_both("TK");
+                index + 2
+            }
+        } else if self.contains(index, &["DT", "DD"]) {
+            self.add_both("T");
+            index + 2
+        } else {
+            self.add_both("T");
+            index + 1
+        }
+    }
+
+    fn g(&mut self, index: isize) -> isize {
+        let next = self.at(index + 1);
+
+        if next == 'H' {
+            self.gh(index)
+        } else if next == 'N' {
+            if index == 1 && self.is_vowel_at(0) && !self.slavo_germanic {
+                self.add("KN", "N");
+            } else if !self.contains(index + 2, &["EY"]) && !self.slavo_germanic {
+                self.add("N", "KN");
+            } else {
+                self.add_both("KN");
+            }
+            index + 2
+        } else if self.contains(index + 1, &["LI"]) && !self.slavo_germanic {
+            self.add("KL", "L");
+            index + 2
+        } else if self.g_is_hard_or_soft(index) {
+            self.add("K", "J");
+            index + 2
+        } else if self.contains(index + 1, &["E", "I", "Y"])
+            || self.contains(index - 1, &["AGGI", "OGGI"])
+        {
+            if self.contains(0, &["VAN ", "VON "])
+                || self.contains(0, &["SCH"])
+                || self.contains(index + 1, &["ET"])
+            {
+                self.add_both("K");
+            } else if self.contains(index + 1, &["IER"]) {
+                self.add_both("J");
+            } else {
+                self.add("J", "K");
+            }
+            index + 2
+        } else if next == 'G' {
+            self.add_both("K");
+            index + 2
+        } else {
+            self.add_both("K");
+            index + 1
+        }
+    }
+
+    // an initial `g` as in `gerald` or `gypsy`, or a `g` before `er` or `y` as
+    // in `tiger`, which can be either
+    fn g_is_hard_or_soft(&self, index: isize) -> bool {
+        let next = self.at(index + 1);
+        let initial = index == 0
+            && (next == 'Y'
+                || self.contains(
+                    index + 1,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ));
+
+        initial
+            || ((self.contains(index + 1, &["ER"]) || next == 'Y')
+                && !self.contains(0, &["DANGER", "RANGER", "MANGER"])
+                && !self.contains(index - 1, &["E", "I"])
+                && !self.contains(index - 1, &["RGY", "OGY"]))
+    }
+
+    fn gh(&mut self, index: isize) -> isize {
+        if index > 0 && !self.is_vowel_at(index - 1) {
+            self.add_both("K");
+        } else if index == 0 {
+            self.add_both(if self.at(index + 2) == 'I' { "J" } else { "K" });
+        } else if (index > 1 && self.contains(index - 2, &["B", "H", "D"]))
+            || (index > 2 && self.contains(index - 3, &["B", "H", "D"]))
+            || (index > 3 && self.contains(index - 4, &["B", "H"]))
+        {
+            // silent, as in `hugh` and `bough`
+        } else if index > 2
+            && self.at(index - 1) == 'U'
+            && self.contains(index - 3, &["C", "G", "L", "R", "T"])
+        {
+            self.add_both("F");
+        } else if index > 0 && self.at(index - 1) != 'I' {
+            self.add_both("K");
+        }
+        index + 2
+    }
+
+    fn j(&mut self, index: isize) -> isize {
+        if self.contains(index, &["JOSE"]) || self.contains(0, &["SAN "]) {
+            if (index == 0 && self.at(index + 4) == ' ')
+                || self.word.len() == 4
+                || self.contains(0, &["SAN "])
+            {
+                self.add_both("H");
+            } else {
+                self.add("J", "H");
+            }
+            return index + 1;
+        }
+
+        if index == 0 {
+            self.add("J", "A");
+        } else if self.is_vowel_at(index - 1)
+            && !self.slavo_germanic
+            && matches!(self.at(index + 1), 'A' | 'O')
+        {
+            self.add("J", "H");
+        } else if index == self.last() {
+            self.add("J", "");
+        } else if !self.contains(index + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.contains(index - 1, &["S", "K", "L"])
+        {
+            self.add_both("J");
+        }
+
+        index + if self.at(index + 1) == 'J' { 2 } else { 1 }
+    }
+
+    fn l(&mut self, index: isize) -> isize {
+        if self.at(index + 1) != 'L' {
+            self.add_both("L");
+            return index + 1;
+        }
+
+        // a Spanish `ll`, as in `cabrillo` or `gallegos`
+        let last = self.last();
+        let spanish = (index == last - 2 && self.contains(index - 1, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.contains(last - 1, &["AS", "OS"]) || self.contains(last, &["A", "O"]))
+                && self.contains(index - 1, &["ALLE"]));
+
+        if spanish {
+            self.add("L", "");
+        } else {
+            self.add_both("L");
+        }
+        index + 2
+    }
+
+    fn r(&mut self, index: isize) -> isize {
+        // a silent French `r`, as in `rogier`
+        if index == self.last()
+            && !self.slavo_germanic
+            && self.contains(index - 2, &["IE"])
+            && !self.contains(index - 4, &["ME", "MA"])
+        {
+            self.add("", "R");
+        } else {
+            self.add_both("R");
+        }
+        index + if self.at(index + 1) == 'R' { 2 } else { 1 }
+    }
+
+    fn s(&mut self, index: isize) -> isize {
+        if self.contains(index - 1, &["ISL", "YSL"]) {
+            // silent, as in `island` and `carlisle`
+            index + 1
+        } else if index == 0 && self.contains(index, &["SUGAR"]) {
+            self.add("X", "S");
+            index + 1
+        } else if self.contains(index, &["SH"]) {
+            if self.contains(index + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add_both("S");
+            } else {
+                self.add_both("X");
+            }
+            index + 2
+        } else if self.contains(index, &["SIO", "SIA"]) || self.contains(index, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.add_both("S");
+            } else {
+                self.add("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.contains(index + 1, &["M", "N", "L", "W"]))
+            || self.contains(index + 1, &["Z"])
+        {
+            self.add("S", "X");
+            index
+                + if self.contains(index + 1, &["Z"]) {
+                    2
+                } else {
+                    1
+                }
+        } else if self.contains(index, &["SC"]) {
+            self.sc(index)
+        } else {
+            // a silent French `s`, as in `artois`
+            if index == self.last() && self.contains(index - 2, &["AI", "OI"]) {
+                self.add("", "S");
+            } else {
+                self.add_both("S");
+            }
+            index
+                + if self.contains(index + 1, &["S", "Z"]) {
+                    2
+                } else {
+                    1
+                }
+        }
+    }
+
+    fn sc(&mut self, index: isize) -> isize {
+        if self.at(index + 2) == 'H' {
+            if self.contains(index + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                if self.contains(index + 3, &["ER", "EN"]) {
+                    self.add("X", "SK");
+                } else {
+                    self.add_both("SK");
+                }
+            } else if index == 0 && !self.is_vowel_at(3) && self.at(3) != 'W' {
+                self.add("X", "S");
+            } else {
+                self.add_both("X");
+            }
+        } else if self.contains(index + 2, &["I", "E", "Y"]) {
+            self.add_both("S");
+        } else {
+            self.add_both("SK");
+        }
+        index + 3
+    }
+
+    fn t(&mut self, index: isize) -> isize {
+        if self.contains(index, &["TION"]) || self.contains(index, &["TIA", "TCH"]) {
+            self.add_both("X");
+            index + 3
+        } else if self.contains(index, &["TH"]) || self.contains(index, &["TTH"]) {
+            if self.contains(index + 2, &["OM", "AM"])
+                || self.contains(0, &["VAN ", "VON "])
+                || self.contains(0, &["SCH"])
+            {
+                self.add_both("T");
+            } else {
+                self.add("0", "T");
+            }
+            index + 2
+        } else {
+            self.add_both("T");
+            index
+                + if self.contains(index + 1, &["T", "D"]) {
+                    2
+                } else {
+                    1
+                }
+        }
+    }
+
+    fn w(&mut self, index: isize) -> isize {
+        if self.contains(index, &["WR"]) {
+            self.add_both("R");
+            return index + 2;
+        }
+
+        if index == 0 && (self.is_vowel_at(index + 1) || self.contains(index, &["WH"])) {
+            if self.is_vowel_at(index + 1) {
+                self.add("A", "F");
+            } else {
+                self.add_both("A");
+            }
+            index + 1
+        } else if (index == self.last() && self.is_vowel_at(index - 1))
+            || self.contains(index - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, &["SCH"])
+        {
+            self.add("", "F");
+            index + 1
+        } else if self.contains(index, &["WICZ", "WITZ"]) {
+            self.add("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn x(&mut self, index: isize) -> isize {
+        if index == 0 {
+            self.add_both("S");
+            return index + 1;
+        }
+
+        // a silent French `x`, as in `breaux`
+        let silent = index == self.last()
+            && (self.contains(index - 3, &["IAU", "EAU"])
+                || self.contains(index - 2, &["AU", "OU"]));
+        if !silent {
+            self.add_both("KS");
+        }
+        index
+            + if self.contains(index + 1, &["C", "X"]) {
+                2
+            } else {
+                1
+            }
+    }
+
+    fn z(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'H' {
+            self.add_both("J");
+            return index + 2;
+        }
+
+        if self.contains(index + 1, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && index > 0 && self.at(index - 1) != 'T')
+        {
+            self.add("S", "TS");
+        } else {
+            self.add_both("S");
+        }
+        index + if self.at(index + 1) == 'Z' { 2 } else { 1 }
+    }
+}
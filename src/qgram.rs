@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use crate::{
+    finish_matches,
+    score::max_edits,
+    score_candidate,
+    trie::{keep_best, Candidate, TrieMatch},
+    util::{normalize, normalize_key},
+    FuzzyOptions,
+};
+
+/// An inverted index of the q-grams of every key, narrowing the keys to score
+/// for very large sets of candidates.
+///
+/// By the q-gram lemma, a term of `m` graphemes within `k` edits of a key, or
+/// of part of it with Sellers, shares at least `m - q + 1 - q·k` of its
+/// q-grams with the key, as no edit can break more than `q` of them. Only
+/// the keys sharing that many are scored, so nothing that would reach the
+/// threshold is missed. When the count comes to nothing, as for short terms
+/// at low thresholds, every key is scored.
+pub struct QGramIndex {
+    q: usize,
+    strength: f64,
+    // removed keys leave a `None` behind, so that slots in `postings` stay
+    // valid
+    candidates: Vec<Option<Candidate>>,
+    // the slots of every key of each item
+    slots: HashMap<usize, Vec<usize>>,
+    // the keys each q-gram appears in, and how many times
+    postings: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl Default for QGramIndex {
+    fn default() -> Self {
+        Self {
+            q: 2,
+            strength: 1.0,
+            candidates: vec![],
+            slots: HashMap::new(),
+            postings: HashMap::new(),
+        }
+    }
+}
+
+impl QGramIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many graphemes make a gram, 2 by default. Longer grams narrow the
+    /// keys down further for long terms, but fail to for short ones.
+    pub fn q(mut self, q: usize) -> Self {
+        assert!(q > 0, "q must be positive");
+        self.q = q;
+        self
+    }
+
+    /// How much of the count filter to apply, between 0 and 1, 1 by default.
+    /// Lower strengths ask for fewer shared grams, scoring more keys.
+    pub fn filter_strength(mut self, strength: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&strength),
+            "filter strength must be between 0 and 1, got {strength}"
+        );
+        self.strength = strength;
+        self
+    }
+
+    fn grams(&self, graphemes: &[String]) -> HashMap<String, usize> {
+        let mut grams = HashMap::new();
+        for gram in graphemes.windows(self.q) {
+            *grams.entry(gram.concat()).or_insert(0) += 1;
+        }
+        grams
+    }
+
+    pub(crate) fn add<T, U>(&mut self, index: usize, item: &T, options: &FuzzyOptions<T, U>) {
+        let keys = (options.key_selector)(item);
+
+        for (key_index, key) in keys.into_iter().enumerate() {
+            for normalized in normalize_key(key, options) {
+                let slot = self.candidates.len();
+                for (gram, count) in self.grams(&normalized.normal) {
+                    self.postings.entry(gram).or_default().push((slot, count));
+                }
+
+                self.slots.entry(index).or_default().push(slot);
+                self.candidates.push(Some(Candidate {
+                    index,
+                    key_index,
+                    normalized,
+                }));
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) {
+        for slot in self.slots.remove(&index).unwrap_or_default() {
+            let Some(candidate) = self.candidates[slot].take() else {
+                continue;
+            };
+
+            for gram in self.grams(&candidate.normalized.normal).into_keys() {
+                if let Some(postings) = self.postings.get_mut(&gram) {
+                    postings.retain(|&(other, _)| other != slot);
+                    if postings.is_empty() {
+                        self.postings.remove(&gram);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes every key, keeping the settings.
+    pub(crate) fn clear(&mut self) {
+        self.candidates.clear();
+        self.slots.clear();
+        self.postings.clear();
+    }
+
+    pub(crate) fn candidates(&self) -> impl Iterator<Item = &Candidate> {
+        self.candidates.iter().flatten()
+    }
+
+    /// The keys sharing enough q-grams with `term` to reach the threshold, or
+    /// `None` when they all have to be scored.
+    pub(crate) fn filter<T, U>(
+        &self,
+        term: &[String],
+        options: &FuzzyOptions<T, U>,
+    ) -> Option<Vec<&Candidate>> {
+        // a transposition breaks the grams of both graphemes it moves
+        let edits = max_edits(term.len(), options, 2.0)?;
+        let grams = (term.len() + 1).saturating_sub(self.q);
+        let required = grams.saturating_sub(self.q * edits) as f64 * self.strength;
+        let required = required.ceil() as usize;
+        if required == 0 {
+            return None;
+        }
+
+        let mut shared = HashMap::new();
+        for (gram, count) in self.grams(term) {
+            for &(slot, key_count) in self.postings.get(&gram).into_iter().flatten() {
+                *shared.entry(slot).or_insert(0) += count.min(key_count);
+            }
+        }
+
+        let mut slots = shared
+            .into_iter()
+            .filter(|&(_, shared)| shared >= required)
+            .map(|(slot, _)| slot)
+            .collect::<Vec<_>>();
+        slots.sort_unstable();
+
+        Some(
+            slots
+                .into_iter()
+                .filter_map(|slot| self.candidates[slot].as_ref())
+                .collect(),
+        )
+    }
+
+    pub(crate) fn search<T, U>(&self, term: &str, options: &FuzzyOptions<T, U>) -> Vec<TrieMatch> {
+        let normal_term = normalize(term.to_string(), options).normal;
+        let candidates = self
+            .filter(&normal_term, options)
+            .unwrap_or_else(|| self.candidates().collect());
+
+        let mut results = HashMap::new();
+        for candidate in candidates {
+            let result = score_candidate(term, &normal_term, candidate, options);
+            if result.score >= options.threshold {
+                keep_best(&mut results, result);
+            }
+        }
+
+        finish_matches(results, options)
+    }
+}
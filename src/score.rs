@@ -0,0 +1,300 @@
+use crate::{numeric, util::Normalized, EditCosts, FuzzyOptions, MatchKind, NumericMatch};
+
+pub(crate) struct ScoreResult {
+    pub(crate) score: f64,
+    pub(crate) score_index: usize,
+}
+
+/// The scoring half of the options: which edit distance to use, and what its
+/// operations cost. Rows of the matrix are term graphemes and columns are
+/// candidate graphemes, so that the trie can fill in one column per node.
+pub(crate) struct Scorer<'a> {
+    pub(crate) use_damerau: bool,
+    pub(crate) use_sellers: bool,
+    pub(crate) costs: &'a EditCosts,
+    pub(crate) numeric_tokens: Option<NumericMatch>,
+}
+
+impl<'a> Scorer<'a> {
+    pub(crate) fn new<T, U>(options: &'a FuzzyOptions<T, U>) -> Self {
+        Self {
+            use_damerau: options.use_damerau,
+            use_sellers: options.use_sellers,
+            costs: &options.edit_costs,
+            numeric_tokens: options.numeric_tokens,
+        }
+    }
+
+    pub(crate) fn init_rows(&self, row_count: usize, column_count: usize) -> Vec<Vec<f64>> {
+        let mut rows = vec![vec![0.0; column_count]; row_count];
+        for (i, row) in rows.iter_mut().enumerate() {
+            row[0] = i as f64 * self.costs.delete;
+        }
+
+        if !self.use_sellers {
+            for (j, cell) in rows[0].iter_mut().enumerate() {
+                *cell = j as f64 * self.costs.insert;
+            }
+        }
+
+        rows
+    }
+
+    pub(crate) fn substitution(&self, term: &str, candidate: &str) -> f64 {
+        match self.numeric_tokens {
+            Some(mode) if numeric::is_number(term) && numeric::is_number(candidate) => {
+                if numeric::agree(term, candidate, mode) {
+                    0.0
+                } else {
+                    self.costs.substitute
+                }
+            }
+            _ => self.costs.substitution(term, candidate),
+        }
+    }
+
+    /// Fills in column `j + 1` of the matrix, which holds the distances after
+    /// consuming `candidate[j]`.
+    pub(crate) fn score_column<S: AsRef<str>>(
+        &self,
+        term: &[String],
+        candidate: &[S],
+        rows: &mut [Vec<f64>],
+        j: usize,
+    ) {
+        self.score_column_from(term, candidate, rows, j, 0);
+    }
+
+    /// Fills in column `j + 1` below its first `from + 1` rows, which only
+    /// depend on the first `from` graphemes of the term and so can be shared
+    /// by terms starting with them.
+    pub(crate) fn score_column_from<S: AsRef<str>>(
+        &self,
+        term: &[String],
+        candidate: &[S],
+        rows: &mut [Vec<f64>],
+        j: usize,
+        from: usize,
+    ) {
+        let current = candidate[j].as_ref();
+        let previous = j.checked_sub(1).map(|j| candidate[j].as_ref());
+
+        for i in from..term.len() {
+            let cost = self.substitution(&term[i], current);
+
+            let mut min = rows[i + 1][j] + self.costs.insert;
+            min = min.min(rows[i][j + 1] + self.costs.delete);
+            min = min.min(rows[i][j] + cost);
+
+            if self.use_damerau
+                && i > 0
+                && Some(term[i].as_str()) == previous
+                && term[i - 1] == current
+            {
+                let cost = if cost == 0.0 {
+                    0.0
+                } else {
+                    self.costs.transpose
+                };
+                min = min.min(rows[i - 1][j - 1] + cost);
+            }
+
+            rows[i + 1][j + 1] = min;
+        }
+    }
+
+    /// The distance a key needs to stay at or under to score at least
+    /// `threshold`, given the longest the key could be.
+    pub(crate) fn max_distance(
+        &self,
+        threshold: f64,
+        term_length: usize,
+        key_length: usize,
+    ) -> f64 {
+        let length = if self.use_sellers {
+            term_length
+        } else {
+            term_length.max(key_length)
+        };
+
+        // leave room for rounding in the fractional costs
+        (1.0 - threshold) * length as f64 + 1e-9
+    }
+
+    pub(crate) fn get_score(&self, rows: &[Vec<f64>], length: usize) -> ScoreResult {
+        if self.use_sellers {
+            get_sellers_score(rows, length)
+        } else {
+            get_lev_score(rows, length)
+        }
+    }
+
+    /// Finds the matched `(start, length)` range of candidate graphemes.
+    pub(crate) fn get_match(
+        &self,
+        rows: &[Vec<f64>],
+        score_index: usize,
+        length: usize,
+    ) -> (usize, usize) {
+        if self.use_sellers {
+            walk_back(rows, score_index)
+        } else {
+            (0, length - 1)
+        }
+    }
+
+    /// Fills in the whole matrix for a key.
+    pub(crate) fn rows(&self, term: &[String], key: &[String]) -> Vec<Vec<f64>> {
+        let mut rows = self.init_rows(term.len() + 1, key.len() + 1);
+        for j in 0..key.len() {
+            self.score_column(term, key, &mut rows, j);
+        }
+        rows
+    }
+
+    /// Scores a single normalized key, returning the score and the matched
+    /// range of its graphemes.
+    pub(crate) fn score(&self, term: &[String], candidate: &Normalized) -> (f64, usize, usize) {
+        let length = candidate.normal.len() + 1;
+        let rows = self.rows(term, &candidate.normal);
+
+        let ScoreResult { score, score_index } = self.get_score(&rows, length);
+        let (start, match_length) = self.get_match(&rows, score_index, length);
+        (score, start, match_length)
+    }
+}
+
+/// The most edits a key can be from a term of `term_length` graphemes and
+/// still score above the threshold, with a transposition counting for
+/// `transposition` edits. `None` when the options don't bound it, so that
+/// every key has to be scored.
+pub(crate) fn max_edits<T, U>(
+    term_length: usize,
+    options: &FuzzyOptions<T, U>,
+    transposition: f64,
+) -> Option<usize> {
+    let (slack, cheapest) = edit_slack(options, transposition)?;
+
+    // With Sellers, a key scores `1 - cost / term`. Without, it scores
+    // `1 - cost / max(term, key)`, and the key is no longer than the term plus
+    // the edits, so `e` edits have to satisfy `cheapest * e <= slack * (term +
+    // e)`.
+    let edits = if options.use_sellers {
+        if cheapest <= 0.0 {
+            return None;
+        }
+        slack * term_length as f64 / cheapest
+    } else {
+        whole_edits(term_length, slack, cheapest)?
+    };
+
+    Some((edits + 1e-9) as usize)
+}
+
+/// The most edits any term that scores above the threshold against the whole
+/// of a key of `key_length` graphemes can be from it, as [`max_edits`] is for
+/// a term.
+pub(crate) fn max_key_edits<T, U>(
+    key_length: usize,
+    options: &FuzzyOptions<T, U>,
+    transposition: f64,
+) -> Option<usize> {
+    let (slack, cheapest) = edit_slack(options, transposition)?;
+
+    // the term is no longer than the key plus the edits, which bounds them as
+    // it does a key without Sellers
+    let edits = whole_edits(key_length, slack, cheapest)?;
+    Some((edits + 1e-9) as usize)
+}
+
+// how far below a perfect score a match can fall, and what the cheapest edit
+// costs, or `None` when the options don't bound the edits
+fn edit_slack<T, U>(options: &FuzzyOptions<T, U>, transposition: f64) -> Option<(f64, f64)> {
+    if options.match_kind != MatchKind::Fuzzy {
+        return None;
+    }
+    // agreeing numbers substitute for free
+    if options.numeric_tokens.is_some() {
+        return None;
+    }
+
+    // agreeing phonetic codes can lift a text score up to the threshold
+    let threshold = match options.phonetic {
+        Some(_) if options.phonetic_weight >= 1.0 => return None,
+        Some(_) => (options.threshold - options.phonetic_weight) / (1.0 - options.phonetic_weight),
+        None => options.threshold,
+    };
+
+    let costs = &options.edit_costs;
+    let mut cheapest = costs.cheapest_edit();
+    if options.use_damerau {
+        cheapest = cheapest.min(costs.transpose / transposition);
+    }
+
+    Some((1.0 - threshold, cheapest))
+}
+
+// the `e` satisfying `cheapest * e <= slack * (length + e)`
+fn whole_edits(length: usize, slack: f64, cheapest: f64) -> Option<f64> {
+    (cheapest > slack).then(|| slack * length as f64 / (cheapest - slack))
+}
+
+fn get_lev_score(rows: &[Vec<f64>], length: usize) -> ScoreResult {
+    let last_cell = rows[rows.len() - 1][length - 1];
+    let score_length = rows.len().max(length) - 1;
+
+    let score = if score_length == 0 {
+        1.0
+    } else {
+        1.0 - last_cell / score_length as f64
+    };
+
+    ScoreResult {
+        score: score.max(0.0),
+        score_index: length - 1,
+    }
+}
+
+fn get_sellers_score(rows: &[Vec<f64>], length: usize) -> ScoreResult {
+    // the search term was empty, so everything matches perfectly
+    if rows.len() == 1 {
+        return ScoreResult {
+            score: 1.0,
+            score_index: 0,
+        };
+    }
+
+    let last_row = &rows[rows.len() - 1];
+    let mut min_value = last_row[0];
+    let mut min_index = 0;
+    for (i, &value) in last_row.iter().enumerate().take(length).skip(1) {
+        if value < min_value {
+            min_value = value;
+            min_index = i;
+        }
+    }
+
+    ScoreResult {
+        score: (1.0 - min_value / (rows.len() - 1) as f64).max(0.0),
+        score_index: min_index,
+    }
+}
+
+// walks back up the matrix to find where the best substring match starts
+fn walk_back(rows: &[Vec<f64>], score_index: usize) -> (usize, usize) {
+    if score_index == 0 {
+        return (0, 0);
+    }
+
+    let mut start = score_index;
+    let mut i = rows.len() - 2;
+    while i > 0 && start > 1 {
+        let row = &rows[i];
+        if row[start] >= row[start - 1] {
+            start -= 1;
+        }
+        i -= 1;
+    }
+
+    (start - 1, score_index - start + 1)
+}
@@ -1,29 +1,493 @@
-use crate::{FuzzyOptions, MatchData, SearchResult};
+use std::{collections::HashMap, sync::OnceLock};
 
-#[allow(dead_code)]
-pub struct Searcher<'a, T> {
-    candidates: Vec<T>,
+use crate::{
+    boost_matches,
+    explain::{boost_explanation, explain_item},
+    resolve_matches,
+    score::Scorer,
+    sort_matches,
+    suggest::{suggest, Vocabulary},
+    trie::{compare_matches, TrieMatch},
+    util::normalize,
+    Explanation, FuzzyOptions, Index, IndexKind, MatchData, ScoreBlender, SearchResult, SortKind,
+    Suggestion, Synonyms, Trie,
+};
+
+// how many empty slots there must be, as well as more than candidates,
+// before they are compacted
+const MIN_COMPACTED: usize = 64;
+
+pub struct Searcher<T, I = Trie> {
+    // removed candidates leave a `None` behind, so that indices in the index
+    // stay valid, until there are enough of them to compact
+    candidates: Vec<Option<T>>,
     count: usize,
-    options: FuzzyOptions<'a, T>,
+    options: FuzzyOptions<T>,
+    index: I,
+    synonyms: Option<Synonyms>,
+    score_blender: Option<Box<dyn ScoreBlender<T>>>,
+    suggestion_distance: f64,
+    // built by the first suggestion, then kept up to date
+    vocabulary: OnceLock<Vocabulary>,
+}
+
+impl<T> Searcher<T> {
+    pub fn new<U>(candidates: Vec<T>, options: FuzzyOptions<T, U>) -> Self {
+        Self::with_index(candidates, options, Trie::default())
+    }
 }
 
-#[allow(unused_variables)]
-impl<'a, T> Searcher<'a, T> {
-    pub fn new<U>(candidates: Vec<T>, options: FuzzyOptions<'a, T, U>) -> Self {
-        todo!()
+impl<T> Searcher<T, Box<dyn Index<T>>> {
+    /// Looks candidates up in an index of `kind`, chosen at runtime, as in
+    /// `Searcher::with_index_kind(candidates, options, IndexKind::BkTree)`.
+    pub fn with_index_kind<U>(
+        candidates: Vec<T>,
+        options: FuzzyOptions<T, U>,
+        kind: IndexKind,
+    ) -> Self {
+        Self::with_index(candidates, options, kind.into_index())
+    }
+}
+
+impl<T, I: Index<T>> Searcher<T, I> {
+    /// Looks candidates up in another [`Index`] than the trie.
+    pub fn with_index<U>(candidates: Vec<T>, options: FuzzyOptions<T, U>, mut index: I) -> Self {
+        let options = options.erase_marker();
+        index.build(&candidates, &options);
+
+        Self {
+            count: candidates.len(),
+            candidates: candidates.into_iter().map(Some).collect(),
+            options,
+            index,
+            synonyms: None,
+            score_blender: None,
+            suggestion_distance: 2.0,
+            vocabulary: OnceLock::new(),
+        }
+    }
+
+    /// Also searches for the query with its words replaced by their
+    /// alternatives in `synonyms`, keeping the best match of each candidate.
+    pub fn synonyms(mut self, synonyms: Synonyms) -> Self {
+        self.synonyms = Some(synonyms);
+        self
+    }
+
+    /// Adjusts the score of every match with `score_blender` before ranking,
+    /// as with a [`Frecency`](crate::Frecency) store. Candidates are still
+    /// found by their text score, so blending can't add matches below the
+    /// threshold.
+    pub fn score_blender(mut self, score_blender: impl ScoreBlender<T> + 'static) -> Self {
+        self.score_blender = Some(Box::new(score_blender));
+        self
+    }
+
+    /// Moves the candidates of a searcher already built, along with its
+    /// synonyms and score blender, to an index of `kind`. A new searcher is
+    /// best built [`with_index_kind`](Searcher::with_index_kind), which
+    /// saves indexing the candidates twice.
+    pub fn index_kind(self, kind: IndexKind) -> Searcher<T, Box<dyn Index<T>>> {
+        let mut index = kind.into_index();
+        for (position, candidate) in self.candidates.iter().enumerate() {
+            if let Some(candidate) = candidate {
+                index.add(position, candidate, &self.options);
+            }
+        }
+
+        Searcher {
+            candidates: self.candidates,
+            count: self.count,
+            options: self.options,
+            index,
+            synonyms: self.synonyms,
+            score_blender: self.score_blender,
+            suggestion_distance: self.suggestion_distance,
+            vocabulary: self.vocabulary,
+        }
+    }
+
+    /// How far each word of a query can be from its correction in
+    /// [`suggest`](Self::suggest), 2 by default.
+    pub fn suggestion_distance(mut self, suggestion_distance: f64) -> Self {
+        self.suggestion_distance = suggestion_distance;
+        self
+    }
+
+    pub fn add(&mut self, candidates: Vec<T>) {
+        for candidate in candidates {
+            let index = self.candidates.len();
+            self.index.add(index, &candidate, &self.options);
+            if let Some(vocabulary) = self.vocabulary.get_mut() {
+                vocabulary.add(&candidate, &self.options);
+            }
+            self.candidates.push(Some(candidate));
+            self.count += 1;
+        }
+    }
+
+    /// Removes every candidate matching `predicate`, returning how many were
+    /// removed.
+    pub fn remove_where(&mut self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        let mut removed = 0;
+
+        for index in 0..self.candidates.len() {
+            if self.candidates[index].as_ref().is_some_and(&mut predicate) {
+                self.remove_at(index);
+                removed += 1;
+            }
+        }
+
+        self.compact();
+        removed
+    }
+
+    // Removes the candidate at `index`, leaving its slot empty until the next
+    // `compact`.
+    pub(crate) fn remove_at(&mut self, index: usize) -> Option<T> {
+        let candidate = self.candidates.get_mut(index)?.take()?;
+        self.index.remove(index, &candidate, &self.options);
+        if let Some(vocabulary) = self.vocabulary.get_mut() {
+            vocabulary.remove(&candidate, &self.options);
+        }
+        self.count -= 1;
+        Some(candidate)
+    }
+
+    // Drops the empty slots removals leave behind once they outnumber the
+    // candidates, indexing the candidates again in their new slots. Returns
+    // whether the slots moved.
+    pub(crate) fn compact(&mut self) -> bool {
+        let empty = self.candidates.len() - self.count;
+        if empty < MIN_COMPACTED || empty <= self.count {
+            return false;
+        }
+
+        let candidates = std::mem::take(&mut self.candidates)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        self.index.build(&candidates, &self.options);
+        self.candidates = candidates.into_iter().map(Some).collect();
+        true
+    }
+
+    // The slot the next candidate added goes in.
+    #[cfg(feature = "fs")]
+    pub(crate) fn next_slot(&self) -> usize {
+        self.candidates.len()
+    }
+
+    // The candidates with their slots.
+    #[cfg(feature = "fs")]
+    pub(crate) fn slots(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, candidate)| Some((index, candidate.as_ref()?)))
+    }
+
+    pub fn clear(&mut self) {
+        self.candidates.clear();
+        self.count = 0;
+        self.index.clear();
+        self.vocabulary = OnceLock::new();
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn search_core(&self, term: &str) -> Vec<TrieMatch> {
+        self.index.query(term, &self.candidates, &self.options)
+    }
+
+    // the matches of `term`, with `lookup` finding those of it and of its
+    // expansions in the index
+    fn matches(&self, term: &str, lookup: impl Fn(&str) -> Vec<TrieMatch>) -> Vec<TrieMatch> {
+        let mut results = self.text_matches(term, lookup);
+        boost_matches(
+            &mut results,
+            |index| self.candidates.get(index)?.as_ref(),
+            &self.options,
+        );
+
+        if let Some(blender) = &self.score_blender {
+            for result in &mut results {
+                if let Some(item) = &self.candidates[result.index] {
+                    result.score = blender.blend(item, result.score);
+                }
+            }
+            sort_matches(&mut results, &self.options);
+        }
+
+        results
+    }
+
+    fn text_matches(&self, term: &str, lookup: impl Fn(&str) -> Vec<TrieMatch>) -> Vec<TrieMatch> {
+        let Some(synonyms) = &self.synonyms else {
+            return lookup(term);
+        };
+
+        let mut results = HashMap::new();
+        for result in lookup(term) {
+            results.insert(result.index, result);
+        }
+
+        for expansion in synonyms.expand(term) {
+            for mut result in lookup(&expansion.query) {
+                result.score *= 1.0 - expansion.penalty;
+                if result.score < self.options.threshold {
+                    continue;
+                }
+
+                let better = results
+                    .get(&result.index)
+                    .is_none_or(|existing| compare_matches(&result, existing).is_lt());
+                if better {
+                    result.expansion = Some(expansion.clone());
+                    results.insert(result.index, result);
+                }
+            }
+        }
+
+        let mut results = results.into_values().collect::<Vec<_>>();
+        sort_matches(&mut results, &self.options);
+        results
+    }
+
+    fn resolve(&self, term: impl ToString) -> Vec<MatchData<T>>
+    where
+        T: Clone,
+    {
+        resolve_matches(
+            self.matches(&term.to_string(), |query| self.search_core(query)),
+            |index| self.candidates[index].clone(),
+            &self.options,
+        )
+    }
+
+    /// Explains how `item` scores against `term` in this searcher, including
+    /// synonyms and score blending. The item doesn't have to be in it.
+    pub fn explain(&self, term: impl ToString, item: &T) -> Explanation {
+        let term = term.to_string();
+        let threshold = self.options.threshold;
+        let mut explanation = explain_item(&term, item, &self.options);
+
+        if let Some(synonyms) = &self.synonyms {
+            for expansion in synonyms.expand(&term) {
+                let mut expanded = explain_item(&expansion.query, item, &self.options);
+                expanded.score *= 1.0 - expansion.penalty;
+                expanded.matched &= expanded.score >= threshold;
+                expanded.expansion = Some(expansion);
+
+                let better = !explanation.matched || expanded.score > explanation.score;
+                if expanded.matched && better {
+                    explanation = expanded;
+                }
+            }
+        }
+
+        boost_explanation(&mut explanation, item, &self.options);
+
+        if let Some(blender) = self.score_blender.as_ref().filter(|_| explanation.matched) {
+            explanation.score = blender.blend(item, explanation.score);
+            explanation.blended = Some(explanation.score);
+        }
+
+        explanation
+    }
+
+    /// Suggests up to `count` corrections of `term`, correcting each of its
+    /// words to a word of the keys. Words are compared whole, by Levenshtein
+    /// or Damerau distance as set in the options, and suggestions are ranked
+    /// by distance and then by how often their words appear. A term whose
+    /// words are all known is suggested as is, with a distance of 0.
+    pub fn suggest(&self, term: impl ToString, count: usize) -> Vec<Suggestion> {
+        let term = normalize(term.to_string(), &self.options).normal;
+        let vocabulary = self
+            .vocabulary
+            .get_or_init(|| Vocabulary::new(self.candidates.iter().flatten(), &self.options));
+        let scorer = Scorer {
+            use_sellers: false,
+            ..Scorer::new(&self.options)
+        };
+
+        suggest(&term, vocabulary, &scorer, self.suggestion_distance, count)
+    }
+
+    pub fn search(&self, term: impl ToString) -> Result<Vec<T>, SearchResult>
+    where
+        T: Clone,
+    {
+        Ok(self
+            .resolve(term)
+            .into_iter()
+            .map(|data| data.item)
+            .collect())
+    }
+
+    /// The first `k` results of [`search`](Self::search). The crate's
+    /// backends still find every match and cut them to `k`, so this saves
+    /// resolving the rest rather than finding them. An index overriding
+    /// [`Index::query_top_k`] is only asked for `k` matches when synonyms,
+    /// boosts, score blending or a custom sort can't reorder them.
+    pub fn search_top_k(&self, term: impl ToString, k: usize) -> Result<Vec<T>, SearchResult>
+    where
+        T: Clone,
+    {
+        let reordered = self.synonyms.is_some()
+            || self.options.boost.is_some()
+            || self.score_blender.is_some()
+            || matches!(self.options.sort_by, SortKind::Custom(_));
+        if reordered {
+            let mut results = self.search(term)?;
+            results.truncate(k);
+            return Ok(results);
+        }
+
+        let results = self
+            .index
+            .query_top_k(&term.to_string(), k, &self.candidates, &self.options);
+        Ok(resolve_matches(
+            results,
+            |index| self.candidates[index].clone(),
+            &self.options,
+        )
+        .into_iter()
+        .map(|data| data.item)
+        .collect())
     }
 
-    pub fn search(&self, term: impl ToString) -> Result<Vec<T>, SearchResult> {
-        todo!()
+    /// Searches for each of `terms`, returning their results in the same
+    /// order. The terms are sorted and searched in groups sharing a prefix,
+    /// and with the `rayon` feature the groups are searched in parallel. The
+    /// trie visits each node once for all the terms of a group, filling in
+    /// the edit distances of the prefix the terms share once per node, so
+    /// that each term only computes the rows of its own suffix.
+    pub fn search_many<S: ToString>(&self, terms: &[S]) -> Result<Vec<Vec<T>>, SearchResult>
+    where
+        T: Clone + MaybeSendSync,
+        I: MaybeSendSync,
+    {
+        let terms = terms.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        // every distinct query to look up, expansions included
+        let mut queries = vec![];
+        let mut ids = HashMap::new();
+        for term in &terms {
+            let expansions = self
+                .synonyms
+                .iter()
+                .flat_map(|synonyms| synonyms.expand(term))
+                .map(|expansion| expansion.query);
+            for query in std::iter::once(term.clone()).chain(expansions) {
+                ids.entry(query.clone()).or_insert_with(|| {
+                    queries.push(query);
+                    queries.len() - 1
+                });
+            }
+        }
+
+        let groups = group_by_prefix(&queries, &self.options);
+        let found = map_all(&groups, |group| {
+            let group = group
+                .iter()
+                .map(|&id| queries[id].as_str())
+                .collect::<Vec<_>>();
+            self.index
+                .query_group(&group, &self.candidates, &self.options)
+        });
+
+        let mut matches = vec![vec![]; queries.len()];
+        for (group, found) in groups.iter().zip(found) {
+            for (&id, results) in group.iter().zip(found) {
+                matches[id] = results;
+            }
+        }
+
+        Ok(map_all(&terms, |term| {
+            let results = self.matches(term, |query| matches[ids[query]].clone());
+            resolve_matches(
+                results,
+                |index| self.candidates[index].clone(),
+                &self.options,
+            )
+            .into_iter()
+            .map(|data| data.item)
+            .collect()
+        }))
     }
 }
 
-#[allow(unused_variables)]
-impl<'a, T> Searcher<'a, T>
+impl<T, I> Searcher<T, I>
 where
-    T: PartialEq + std::fmt::Debug,
+    T: Clone + PartialEq + std::fmt::Debug,
+    I: Index<T>,
 {
     pub fn search_data(&self, term: impl ToString) -> Result<Vec<MatchData<T>>, SearchResult> {
-        todo!()
+        Ok(self.resolve(term))
+    }
+}
+
+// how many leading graphemes the queries searched together share, and how
+// many of them there can be
+const GROUP_PREFIX: usize = 2;
+const GROUP_SIZE: usize = 64;
+
+// Sorts `queries` by their normalized form, grouping those that share a
+// prefix. Each group holds the indices of its queries.
+fn group_by_prefix<T>(queries: &[String], options: &FuzzyOptions<T>) -> Vec<Vec<usize>> {
+    let normal = queries
+        .iter()
+        .map(|query| normalize(query.clone(), options).normal)
+        .collect::<Vec<_>>();
+    let prefix = |id: usize| &normal[id][..normal[id].len().min(GROUP_PREFIX)];
+
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| normal[a].cmp(&normal[b]));
+
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for id in order {
+        match groups.last_mut() {
+            Some(group) if group.len() < GROUP_SIZE && prefix(group[0]) == prefix(id) => {
+                group.push(id)
+            }
+            _ => groups.push(vec![id]),
+        }
     }
+
+    groups
+}
+
+/// `Send + Sync` with the `rayon` feature, which searches in parallel, and
+/// implemented for every type without it.
+#[cfg(feature = "rayon")]
+pub trait MaybeSendSync: Send + Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Send + Sync> MaybeSendSync for T {}
+
+/// `Send + Sync` with the `rayon` feature, which searches in parallel, and
+/// implemented for every type without it.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSendSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSendSync for T {}
+
+#[cfg(feature = "rayon")]
+fn map_all<A: Sync, B: Send>(items: &[A], f: impl Fn(&A) -> B + Sync + Send) -> Vec<B> {
+    use rayon::prelude::*;
+
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn map_all<A, B>(items: &[A], f: impl Fn(&A) -> B) -> Vec<B> {
+    items.iter().map(f).collect()
 }
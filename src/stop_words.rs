@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+/// A language with a built-in list of stop words.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Language {
+    English,
+    French,
+    German,
+    Spanish,
+    Italian,
+    Portuguese,
+    Dutch,
+}
+
+impl Language {
+    fn words(self) -> &'static str {
+        match self {
+            Language::English => include_str!("data/stop_words/english.txt"),
+            Language::French => include_str!("data/stop_words/french.txt"),
+            Language::German => include_str!("data/stop_words/german.txt"),
+            Language::Spanish => include_str!("data/stop_words/spanish.txt"),
+            Language::Italian => include_str!("data/stop_words/italian.txt"),
+            Language::Portuguese => include_str!("data/stop_words/portuguese.txt"),
+            Language::Dutch => include_str!("data/stop_words/dutch.txt"),
+        }
+    }
+}
+
+/// Words too common to be worth matching, such as `the` and `of`. They are
+/// compared regardless of case.
+#[derive(Clone, Debug, Default)]
+pub struct StopWords {
+    words: HashSet<String>,
+}
+
+impl StopWords {
+    pub fn new<S: AsRef<str>>(words: impl IntoIterator<Item = S>) -> Self {
+        Self::default().with(words)
+    }
+
+    /// The built-in list for `language`.
+    pub fn language(language: Language) -> Self {
+        Self::new(language.words().lines())
+    }
+
+    /// Adds `words` to the list.
+    pub fn with<S: AsRef<str>>(mut self, words: impl IntoIterator<Item = S>) -> Self {
+        self.words
+            .extend(words.into_iter().map(|word| word.as_ref().to_lowercase()));
+        self
+    }
+
+    /// Adds the built-in list for `language`.
+    pub fn with_language(self, language: Language) -> Self {
+        self.with(language.words().lines())
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, HashMap},
+};
+
+use crate::{score::Scorer, util::normalize_key, FuzzyOptions};
+
+/// A correction of a whole query, from
+/// [`Searcher::suggest`](crate::Searcher::suggest).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    /// The corrected query, normalized, with its words separated by spaces.
+    pub text: String,
+    /// The sum of the edit distances of its words from the query's.
+    pub distance: f64,
+    /// How often the rarest of its words appears in the keys.
+    pub frequency: usize,
+}
+
+// a word of the keys that a word of the query could be corrected to
+struct Correction<'v> {
+    word: &'v [String],
+    distance: f64,
+    frequency: usize,
+}
+
+fn compare(a: (f64, usize), b: (f64, usize)) -> Ordering {
+    a.0.total_cmp(&b.0).then(b.1.cmp(&a.1))
+}
+
+fn words(graphemes: &[String]) -> impl Iterator<Item = &[String]> {
+    graphemes
+        .split(|grapheme| grapheme.trim().is_empty())
+        .filter(|word| !word.is_empty())
+}
+
+/// Every word of the keys of a searcher, with how often it appears.
+#[derive(Default)]
+pub(crate) struct Vocabulary {
+    words: HashMap<Vec<String>, usize>,
+}
+
+impl Vocabulary {
+    pub(crate) fn new<'i, T: 'i, U>(
+        items: impl Iterator<Item = &'i T>,
+        options: &FuzzyOptions<T, U>,
+    ) -> Self {
+        let mut vocabulary = Self::default();
+        for item in items {
+            vocabulary.add(item, options);
+        }
+        vocabulary
+    }
+
+    pub(crate) fn add<T, U>(&mut self, item: &T, options: &FuzzyOptions<T, U>) {
+        for word in item_words(item, options) {
+            *self.words.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    pub(crate) fn remove<T, U>(&mut self, item: &T, options: &FuzzyOptions<T, U>) {
+        for word in item_words(item, options) {
+            if let Entry::Occupied(mut entry) = self.words.entry(word) {
+                *entry.get_mut() -= 1;
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+            }
+        }
+    }
+
+    // the words within `max_distance` of `word`, closest and most frequent
+    // first
+    fn corrections<'v>(
+        &'v self,
+        word: &'v [String],
+        scorer: &Scorer,
+        max_distance: f64,
+    ) -> Vec<Correction<'v>> {
+        // every grapheme of difference in length costs at least this much
+        let length_cost = scorer.costs.insert.min(scorer.costs.delete);
+
+        let mut corrections = self
+            .words
+            .iter()
+            .filter(|(known, _)| {
+                known.len().abs_diff(word.len()) as f64 * length_cost <= max_distance
+            })
+            .filter_map(|(known, &frequency)| {
+                let distance = scorer.rows(word, known)[word.len()][known.len()];
+                (distance <= max_distance).then_some(Correction {
+                    word: known,
+                    distance,
+                    frequency,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        corrections.sort_by(|a, b| {
+            compare((a.distance, a.frequency), (b.distance, b.frequency))
+                .then_with(|| a.word.cmp(b.word))
+        });
+        corrections
+    }
+}
+
+// the words of every normalized form of every key of `item`
+fn item_words<T, U>(item: &T, options: &FuzzyOptions<T, U>) -> Vec<Vec<String>> {
+    (options.key_selector)(item)
+        .into_iter()
+        .flat_map(|key| normalize_key(key, options))
+        .flat_map(|normalized| {
+            words(&normalized.normal)
+                .map(<[String]>::to_vec)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Corrects `term` word by word, keeping the `count` best combinations after
+/// every word. Words without a correction are kept as typed, with a frequency
+/// of 0, unless none of the words has one.
+pub(crate) fn suggest(
+    term: &[String],
+    vocabulary: &Vocabulary,
+    scorer: &Scorer,
+    max_distance: f64,
+    count: usize,
+) -> Vec<Suggestion> {
+    let mut suggestions: Vec<(Vec<&[String]>, f64, usize)> = vec![(vec![], 0.0, usize::MAX)];
+    let mut corrected = false;
+
+    for word in words(term) {
+        let mut corrections = vocabulary.corrections(word, scorer, max_distance);
+        corrections.truncate(count);
+        corrected |= !corrections.is_empty();
+        if corrections.is_empty() {
+            corrections.push(Correction {
+                word,
+                distance: 0.0,
+                frequency: 0,
+            });
+        }
+
+        suggestions = suggestions
+            .iter()
+            .flat_map(|(words, distance, frequency)| {
+                corrections.iter().map(move |correction| {
+                    let mut words = words.clone();
+                    words.push(correction.word);
+                    (
+                        words,
+                        distance + correction.distance,
+                        correction.frequency.min(*frequency),
+                    )
+                })
+            })
+            .collect();
+        suggestions.sort_by(|a, b| compare((a.1, a.2), (b.1, b.2)));
+        suggestions.truncate(count);
+    }
+
+    if !corrected {
+        return vec![];
+    }
+
+    suggestions
+        .into_iter()
+        .map(|(words, distance, frequency)| Suggestion {
+            text: words
+                .into_iter()
+                .map(|word| word.concat())
+                .collect::<Vec<_>>()
+                .join(" "),
+            distance,
+            frequency,
+        })
+        .collect()
+}
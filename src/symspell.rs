@@ -0,0 +1,195 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    finish_matches,
+    score::{max_edits, max_key_edits},
+    score_candidate,
+    trie::{keep_best, Candidate, TrieMatch},
+    util::{normalize, normalize_key},
+    FuzzyOptions,
+};
+
+/// A symmetric delete index, for correcting short words against a large
+/// vocabulary faster than a trie walk can.
+///
+/// Every key is stored under each string left by deleting some of its
+/// graphemes, as many as a term could be from it and still make the
+/// threshold. A term then only has to look up its own deletions to find the
+/// keys close enough to it, which are scored as usual. This only finds keys
+/// that are close to the whole term, so with Sellers, whose matches can be
+/// anywhere inside longer keys, every key is scored instead. It is meant for
+/// `use_sellers(false)`.
+pub struct SymSpellIndex {
+    max_distance: Option<usize>,
+    prefix_length: usize,
+    // removed keys leave a `None` behind, so that slots in `deletes` stay valid
+    candidates: Vec<Option<Candidate>>,
+    // the slots of every key of each item
+    slots: HashMap<usize, Vec<usize>>,
+    deletes: HashMap<String, Vec<usize>>,
+}
+
+impl Default for SymSpellIndex {
+    fn default() -> Self {
+        Self {
+            max_distance: None,
+            prefix_length: 7,
+            candidates: vec![],
+            slots: HashMap::new(),
+            deletes: HashMap::new(),
+        }
+    }
+}
+
+impl SymSpellIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many graphemes a key can differ from the term by and still be
+    /// found. By default this follows from the threshold, so that no key
+    /// making it is missed. The index grows quickly with it, and a lower
+    /// limit trades matches for a smaller index.
+    pub fn max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// How many leading graphemes of each key are indexed, 7 by default.
+    /// Longer keys are found by their prefix, which keeps the number of
+    /// deletions down.
+    pub fn prefix_length(mut self, prefix_length: usize) -> Self {
+        assert!(prefix_length > 0, "prefix length must be positive");
+        self.prefix_length = prefix_length;
+        self
+    }
+
+    // how many graphemes of a key are deleted, or of a term when `term` is
+    // set, with every one of the prefix deleted when the options don't bound
+    // the edits
+    fn distance<T, U>(
+        &self,
+        graphemes: &[String],
+        term: bool,
+        options: &FuzzyOptions<T, U>,
+    ) -> usize {
+        let edits = if term {
+            max_edits(graphemes.len(), options, 1.0)
+        } else {
+            max_key_edits(graphemes.len(), options, 1.0)
+        };
+
+        self.max_distance
+            .or(edits)
+            .unwrap_or(usize::MAX)
+            .min(graphemes.len().min(self.prefix_length))
+    }
+
+    // every string left by deleting up to `distance` graphemes of the prefix
+    // of `graphemes`
+    fn deletes(&self, graphemes: &[String], distance: usize) -> HashSet<String> {
+        let prefix = &graphemes[..graphemes.len().min(self.prefix_length)];
+        let mut deletes = HashSet::from([prefix.concat()]);
+        let mut level = vec![prefix.to_vec()];
+
+        for _ in 0..distance {
+            let mut next = vec![];
+            for graphemes in &level {
+                for i in 0..graphemes.len() {
+                    let mut deleted = graphemes.clone();
+                    deleted.remove(i);
+                    if deletes.insert(deleted.concat()) {
+                        next.push(deleted);
+                    }
+                }
+            }
+            level = next;
+        }
+
+        deletes
+    }
+
+    pub(crate) fn add<T, U>(&mut self, index: usize, item: &T, options: &FuzzyOptions<T, U>) {
+        let keys = (options.key_selector)(item);
+
+        for (key_index, key) in keys.into_iter().enumerate() {
+            for normalized in normalize_key(key, options) {
+                let slot = self.candidates.len();
+                let distance = self.distance(&normalized.normal, false, options);
+                for delete in self.deletes(&normalized.normal, distance) {
+                    self.deletes.entry(delete).or_default().push(slot);
+                }
+
+                self.slots.entry(index).or_default().push(slot);
+                self.candidates.push(Some(Candidate {
+                    index,
+                    key_index,
+                    normalized,
+                }));
+            }
+        }
+    }
+
+    pub(crate) fn remove<T, U>(&mut self, index: usize, options: &FuzzyOptions<T, U>) {
+        for slot in self.slots.remove(&index).unwrap_or_default() {
+            let Some(candidate) = self.candidates[slot].take() else {
+                continue;
+            };
+
+            let distance = self.distance(&candidate.normalized.normal, false, options);
+            for delete in self.deletes(&candidate.normalized.normal, distance) {
+                if let Some(slots) = self.deletes.get_mut(&delete) {
+                    slots.retain(|&other| other != slot);
+                    if slots.is_empty() {
+                        self.deletes.remove(&delete);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes every key, keeping the settings.
+    pub(crate) fn clear(&mut self) {
+        self.candidates.clear();
+        self.slots.clear();
+        self.deletes.clear();
+    }
+
+    // the keys sharing a deletion with `term`, each once
+    fn lookup(&self, term: &[String], distance: usize) -> impl Iterator<Item = &Candidate> {
+        let mut slots = self
+            .deletes(term, distance)
+            .iter()
+            .filter_map(|delete| self.deletes.get(delete))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        slots.sort_unstable();
+        slots.dedup();
+
+        slots
+            .into_iter()
+            .filter_map(|slot| self.candidates[slot].as_ref())
+    }
+
+    pub(crate) fn search<T, U>(&self, term: &str, options: &FuzzyOptions<T, U>) -> Vec<TrieMatch> {
+        let normal_term = normalize(term.to_string(), options).normal;
+        let mut results = HashMap::new();
+
+        let candidates = if options.use_sellers {
+            self.candidates.iter().flatten().collect()
+        } else {
+            let distance = self.distance(&normal_term, true, options);
+            self.lookup(&normal_term, distance).collect::<Vec<_>>()
+        };
+
+        for candidate in candidates {
+            let result = score_candidate(term, &normal_term, candidate, options);
+            if result.score >= options.threshold {
+                keep_best(&mut results, result);
+            }
+        }
+
+        finish_matches(results, options)
+    }
+}
@@ -0,0 +1,200 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use thiserror::Error;
+
+// queries with many expandable words could otherwise blow up
+const MAX_EXPANSIONS: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum SynonymsError {
+    #[error("failed to read synonyms: {0}")]
+    Io(#[from] io::Error),
+    #[error("line {0} has no alternatives")]
+    MissingAlternatives(usize),
+}
+
+/// How a query was rewritten before a match was scored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expansion {
+    /// The rewritten query.
+    pub query: String,
+    /// The words that were replaced, with what they were replaced by.
+    pub replacements: Vec<(String, String)>,
+    /// The fraction taken off the score for matching an expanded form.
+    pub penalty: f64,
+}
+
+/// Alternatives for the words of a query, such as `street` for `st`, which a
+/// [`Searcher`](crate::Searcher) also tries when searching.
+///
+/// Entries only go one way: `st` expanding to `street` does not make `street`
+/// expand to `st`.
+#[derive(Clone, Debug)]
+pub struct Synonyms {
+    // lowercased words of a term, to the words of each alternative
+    entries: HashMap<Vec<String>, Vec<Vec<String>>>,
+    // the number of words in the longest term
+    longest: usize,
+    penalty: f64,
+}
+
+impl Default for Synonyms {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            longest: 0,
+            penalty: 0.05,
+        }
+    }
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace().map(str::to_lowercase).collect()
+}
+
+impl Synonyms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses one entry per line, either tab-separated, as in
+    /// `st<TAB>street<TAB>saint`, or as `st = street, saint`. Terms and
+    /// alternatives can be several words long. Blank lines and lines starting
+    /// with `#` are skipped.
+    pub fn parse(text: &str) -> Result<Self, SynonymsError> {
+        let mut synonyms = Self::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (term, alternatives) = if line.contains('\t') {
+                let mut columns = line.split('\t');
+                (columns.next().unwrap_or_default(), columns.collect())
+            } else {
+                let (term, alternatives) = line
+                    .split_once('=')
+                    .ok_or(SynonymsError::MissingAlternatives(number + 1))?;
+                (term, alternatives.split(',').collect::<Vec<_>>())
+            };
+
+            let alternatives = alternatives
+                .into_iter()
+                .filter(|alternative| !alternative.trim().is_empty())
+                .collect::<Vec<_>>();
+            if term.trim().is_empty() || alternatives.is_empty() {
+                return Err(SynonymsError::MissingAlternatives(number + 1));
+            }
+
+            synonyms = synonyms.add(term, alternatives);
+        }
+
+        Ok(synonyms)
+    }
+
+    /// Reads and parses a file in the format of [`parse`](Self::parse).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SynonymsError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Adds alternatives for `term`, after any it already has.
+    pub fn add<S: AsRef<str>>(
+        mut self,
+        term: &str,
+        alternatives: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let term = words(term);
+        if term.is_empty() {
+            return self;
+        }
+
+        self.longest = self.longest.max(term.len());
+        let entry = self.entries.entry(term).or_default();
+        for alternative in alternatives {
+            let alternative = words(alternative.as_ref());
+            if !alternative.is_empty() && !entry.contains(&alternative) {
+                entry.push(alternative);
+            }
+        }
+
+        self
+    }
+
+    /// The fraction taken off the score of matches of an expanded query, 0.05
+    /// by default, so that the query as typed wins ties.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&penalty),
+            "penalty must be between 0 and 1, got {penalty}"
+        );
+        self.penalty = penalty;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every rewriting of `term` with some of its words replaced, not
+    /// including `term` itself.
+    pub(crate) fn expand(&self, term: &str) -> Vec<Expansion> {
+        let typed = term.split_whitespace().collect::<Vec<_>>();
+        let words = words(term);
+        let mut expansions = vec![];
+        self.expand_from(&typed, &words, 0, &mut vec![], &mut vec![], &mut expansions);
+        expansions
+    }
+
+    fn expand_from(
+        &self,
+        typed: &[&str],
+        words: &[String],
+        start: usize,
+        query: &mut Vec<String>,
+        replacements: &mut Vec<(String, String)>,
+        expansions: &mut Vec<Expansion>,
+    ) {
+        if expansions.len() >= MAX_EXPANSIONS {
+            return;
+        }
+        if start == words.len() {
+            if !replacements.is_empty() {
+                expansions.push(Expansion {
+                    query: query.join(" "),
+                    replacements: replacements.clone(),
+                    penalty: self.penalty,
+                });
+            }
+            return;
+        }
+
+        query.push(typed[start].to_string());
+        self.expand_from(typed, words, start + 1, query, replacements, expansions);
+        query.pop();
+
+        // longer terms first, so that `tv set` is tried before `tv`
+        for length in (1..=self.longest.min(words.len() - start)).rev() {
+            let term = &words[start..start + length];
+            let Some(alternatives) = self.entries.get(term) else {
+                continue;
+            };
+
+            for alternative in alternatives {
+                query.extend(alternative.iter().cloned());
+                replacements.push((term.join(" "), alternative.join(" ")));
+                self.expand_from(
+                    typed,
+                    words,
+                    start + length,
+                    query,
+                    replacements,
+                    expansions,
+                );
+                replacements.pop();
+                query.truncate(query.len() - alternative.len());
+            }
+        }
+    }
+}
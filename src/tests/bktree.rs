@@ -0,0 +1,99 @@
+use super::assert_same_as_brute_force;
+use crate::{
+    BkTree, EditCosts, FuzzyOptions, MatchKind, PhoneticAlgorithm, Searcher, SubstitutionTable,
+};
+
+#[test]
+fn should_find_the_same_matches_as_brute_force() {
+    for threshold in [0.0, 0.3, 0.6, 0.8, 1.0] {
+        assert_same_as_brute_force(
+            || {
+                FuzzyOptions::default()
+                    .use_sellers(false)
+                    .threshold(threshold)
+            },
+            BkTree::new(),
+        );
+        assert_same_as_brute_force(
+            || {
+                FuzzyOptions::default()
+                    .use_sellers(false)
+                    .threshold(threshold)
+                    .use_damerau(false)
+            },
+            BkTree::new(),
+        );
+    }
+}
+
+#[test]
+fn should_widen_the_search_for_cheap_edits() {
+    assert_same_as_brute_force(
+        || {
+            FuzzyOptions::default()
+                .use_sellers(false)
+                .threshold(0.5)
+                .edit_costs(
+                    EditCosts::default()
+                        .transpose(0.5)
+                        .substitutions(SubstitutionTable::new().with("b", "c", 0.25)),
+                )
+        },
+        BkTree::new(),
+    );
+    assert_same_as_brute_force(
+        || {
+            FuzzyOptions::default()
+                .use_sellers(false)
+                .threshold(0.7)
+                .edit_costs(EditCosts::default().insert(0.2))
+        },
+        BkTree::new(),
+    );
+    assert_same_as_brute_force(
+        || {
+            FuzzyOptions::default()
+                .use_sellers(false)
+                .threshold(0.7)
+                .phonetic(PhoneticAlgorithm::Soundex)
+        },
+        BkTree::new(),
+    );
+}
+
+#[test]
+fn should_fall_back_to_scoring_every_key() {
+    assert_same_as_brute_force(FuzzyOptions::default, BkTree::new());
+    assert_same_as_brute_force(
+        || {
+            FuzzyOptions::default()
+                .use_sellers(false)
+                .match_kind(MatchKind::Identifier)
+        },
+        BkTree::new(),
+    );
+}
+
+#[test]
+fn should_add_and_remove_candidates() {
+    let mut searcher = Searcher::with_index(
+        vec!["apple", "apply", "maple"],
+        FuzzyOptions::default().use_sellers(false),
+        BkTree::new(),
+    );
+    assert_eq!(searcher.search("appel"), Ok(vec!["apple", "apply"]));
+
+    assert_eq!(searcher.remove_where(|word| *word == "apple"), 1);
+    assert_eq!(searcher.search("appel"), Ok(vec!["apply"]));
+
+    // the removed key still routes the search to the keys below it
+    searcher.add(vec!["appeal", "apple"]);
+    assert_eq!(
+        searcher.search("appel"),
+        Ok(vec!["appeal", "apple", "apply"])
+    );
+
+    searcher.clear();
+    assert!(searcher.is_empty());
+    assert_eq!(searcher.search("appel"), Ok(vec![]));
+}
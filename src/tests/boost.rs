@@ -0,0 +1,108 @@
+use crate::{search, search_data, BoostMode, FuzzyOptions, Searcher, Synonyms};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Product {
+    name: &'static str,
+    popularity: f64,
+}
+
+fn products() -> Vec<Product> {
+    vec![
+        Product {
+            name: "usb cable",
+            popularity: 0.5,
+        },
+        Product {
+            name: "usb hub",
+            popularity: 2.0,
+        },
+        Product {
+            name: "desk lamp",
+            popularity: 100.0,
+        },
+    ]
+}
+
+fn by_popularity(mode: BoostMode) -> FuzzyOptions<Product> {
+    FuzzyOptions::default_with_key_selector(|product: &Product| vec![product.name.to_string()])
+        .boost(|product| product.popularity)
+        .boost_mode(mode)
+}
+
+#[test]
+fn should_multiply_scores_by_the_boost() {
+    let results = search_data("usb", products(), &by_popularity(BoostMode::Multiply)).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].item.name, "usb hub");
+    assert_eq!(results[0].score, 2.0);
+    assert_eq!(results[0].boost, Some(2.0));
+    assert_eq!(results[1].score, 0.5);
+    assert_eq!(results[1].boost, Some(0.5));
+}
+
+#[test]
+fn should_add_the_boost_to_scores() {
+    let options = by_popularity(BoostMode::Add);
+    let results = search_data("usb", products(), &options).unwrap();
+    assert_eq!(results[0].item.name, "usb hub");
+    assert_eq!(results[0].score, 3.0);
+    assert_eq!(results[1].item.name, "usb cable");
+    assert_eq!(results[1].score, 1.5);
+}
+
+#[test]
+fn should_not_lift_candidates_below_the_threshold() {
+    let names = search("usb", products(), &by_popularity(BoostMode::Add))
+        .unwrap()
+        .into_iter()
+        .map(|product| product.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["usb hub", "usb cable"]);
+}
+
+#[test]
+fn should_boost_in_searchers() {
+    let searcher = Searcher::new(products(), by_popularity(BoostMode::Multiply));
+    assert_eq!(searcher.search("usb").unwrap()[0].name, "usb hub");
+}
+
+#[test]
+fn should_leave_scores_alone_without_a_boost() {
+    let results = search_data("usb", vec!["usb hub"], &FuzzyOptions::default()).unwrap();
+    assert_eq!(results[0].score, 1.0);
+    assert_eq!(results[0].boost, None);
+}
+
+#[test]
+fn should_boost_after_the_synonym_penalty() {
+    let searcher = |penalty: f64, mode: BoostMode, boost: f64| {
+        Searcher::new(
+            vec!["television"],
+            FuzzyOptions::default()
+                .threshold(0.6)
+                .boost(move |_: &&str| boost)
+                .boost_mode(mode),
+        )
+        .synonyms(Synonyms::new().add("tv", ["television"]).penalty(penalty))
+    };
+
+    // the expansion scores 0.5 on its text, below the threshold
+    for (mode, boost) in [(BoostMode::Add, 0.5), (BoostMode::Multiply, 2.0)] {
+        let searcher = searcher(0.5, mode, boost);
+        assert_eq!(searcher.search("tv"), Ok(vec![]));
+
+        let explanation = searcher.explain("tv", &"television");
+        assert!(!explanation.matched);
+        assert_eq!(explanation.boost, None);
+    }
+
+    // at 0.8 it makes the threshold, and is boosted from there
+    let searcher = searcher(0.2, BoostMode::Add, 0.5);
+    let results = searcher.search_data("tv").unwrap();
+    assert_eq!(results[0].score, 1.3);
+    assert_eq!(results[0].boost, Some(0.5));
+
+    let explanation = searcher.explain("tv", &"television");
+    assert!(explanation.matched);
+    assert_eq!(explanation.score, 1.3);
+}
@@ -0,0 +1,73 @@
+use more_asserts::assert_lt;
+
+use crate::{fuzzy, fuzzy_data, util::normalize, CaseMode, FuzzyOptions, Locale};
+
+fn normal(string: &str, case_mode: CaseMode) -> String {
+    let options = FuzzyOptions::<String>::default().case_mode(case_mode);
+    normalize(string.to_string(), &options).key()
+}
+
+#[test]
+fn should_fold_expanding_graphemes() {
+    let options = FuzzyOptions::default().case_mode(CaseMode::Fold);
+    assert_eq!(fuzzy("STRASSE", "Straße", &options), 1.0);
+    assert_eq!(fuzzy("straße", "STRASSE", &options), 1.0);
+
+    assert_lt!(fuzzy("STRASSE", "Straße", &FuzzyOptions::default()), 1.0);
+}
+
+#[test]
+fn should_map_expanded_graphemes_to_the_original() {
+    let options = FuzzyOptions::default().case_mode(CaseMode::Fold);
+
+    let data = fuzzy_data("strasse", "Die Straße", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, 4);
+    assert_eq!(data.match_length, "Straße".len());
+
+    // half of an expansion still highlights the whole grapheme
+    let data = fuzzy_data("stras", "Die Straße", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, 4);
+    assert_eq!(data.match_length, "Straß".len());
+}
+
+#[test]
+fn should_handle_final_sigma() {
+    assert_eq!(normal("ΟΔΟΣ ΣΟΦΟΣ", CaseMode::Lower), "οδος σοφος");
+    assert_eq!(normal("ΟΔΟΣ ΣΟΦΟΣ", CaseMode::Fold), "οδοσ σοφοσ");
+
+    let options = FuzzyOptions::default().case_mode(CaseMode::Fold);
+    assert_eq!(fuzzy("οδος", "ΟΔΟΣ", &options), 1.0);
+}
+
+#[test]
+fn should_tailor_turkish_and_azeri() {
+    for locale in [Locale::Turkish, Locale::Azeri] {
+        let case_mode = CaseMode::FoldLocale(locale);
+        assert_eq!(normal("İSTANBUL", case_mode), "istanbul");
+        assert_eq!(normal("DIŞ", case_mode), "dış");
+        assert_eq!(normal("I\u{307}", case_mode), "i");
+
+        let options = FuzzyOptions::default().case_mode(case_mode);
+        assert_eq!(fuzzy("dış", "DIŞ", &options), 1.0);
+    }
+
+    assert_eq!(normal("DIŞ", CaseMode::Fold), "diş");
+}
+
+#[test]
+fn should_tailor_lithuanian() {
+    let case_mode = CaseMode::FoldLocale(Locale::Lithuanian);
+    assert_eq!(normal("Ì", case_mode), "i\u{307}\u{300}");
+    assert_eq!(normal("J\u{303}", case_mode), "j\u{307}\u{303}");
+    assert_eq!(normal("JONAS", case_mode), "jonas");
+}
+
+#[test]
+fn should_keep_case_when_not_ignoring_it() {
+    let options = FuzzyOptions::<String>::default()
+        .ignore_case(false)
+        .case_mode(CaseMode::Fold);
+    assert_eq!(normalize("Straße".to_string(), &options).key(), "Straße");
+}
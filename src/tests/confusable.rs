@@ -0,0 +1,55 @@
+use more_asserts::assert_lt;
+
+use crate::{confusable_matches, fuzzy, FuzzyOptions, Substitution};
+
+#[test]
+fn should_fold_confusables() {
+    let options = FuzzyOptions::default().fold_confusables(true);
+    assert_eq!(fuzzy("paypal", "p\u{430}ypal", &options), 1.0);
+    assert_eq!(fuzzy("paypal", "PAYPA1", &options), 1.0);
+    assert_eq!(fuzzy("google", "g00gle", &options), 1.0);
+    assert_eq!(fuzzy("amazon", "arnazon", &options), 1.0);
+
+    assert_lt!(
+        fuzzy("paypal", "p\u{430}ypal", &FuzzyOptions::default()),
+        1.0
+    );
+}
+
+#[test]
+fn should_report_lookalikes() {
+    let candidates = vec!["p\u{430}ypal", "paypa1", "paypals", "pineapple", "paypal"];
+    let matches = confusable_matches("paypal", candidates);
+
+    let items = matches.iter().map(|m| m.item).collect::<Vec<_>>();
+    assert_eq!(items, vec!["p\u{430}ypal", "paypa1", "paypal", "paypals"]);
+
+    assert_eq!(matches[0].score, 1.0);
+    assert_eq!(
+        matches[0].substitutions,
+        vec![Substitution {
+            index: 1,
+            original: "\u{430}".into(),
+            prototype: "a".into(),
+        }]
+    );
+    assert_eq!(
+        matches[1].substitutions,
+        vec![Substitution {
+            index: 5,
+            original: "1".into(),
+            prototype: "l".into(),
+        }]
+    );
+    assert!(matches[2].substitutions.is_empty());
+
+    assert_lt!(matches[3].score, 1.0);
+}
+
+#[test]
+fn should_not_report_characters_shared_with_the_term() {
+    // `m` reads as `rn`, but that is no disguise when the term has one too
+    let matches = confusable_matches("amazon", vec!["amazon", "arnazon"]);
+    assert_eq!(matches.len(), 2);
+    assert!(matches.iter().all(|m| m.substitutions.is_empty()));
+}
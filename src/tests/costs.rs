@@ -0,0 +1,141 @@
+use more_asserts::{assert_gt, assert_lt};
+
+use super::random_strings;
+use crate::{fuzzy, search, EditCosts, FuzzyOptions, Searcher, SubstitutionTable};
+
+#[test]
+fn should_score_like_plain_levenshtein_by_default() {
+    let options = FuzzyOptions::default().edit_costs(EditCosts::default());
+    assert_eq!(fuzzy("help", "hello", &options), 0.75);
+    assert_eq!(fuzzy("abcd", "acbd", &options), 0.75);
+}
+
+#[test]
+fn should_prefer_adjacent_key_typos() {
+    let options = FuzzyOptions::default()
+        .edit_costs(EditCosts::default().substitutions(SubstitutionTable::qwerty(0.25)));
+
+    assert_eq!(fuzzy("hrllo", "hello", &options), 0.95);
+    assert_eq!(fuzzy("hqllo", "hello", &options), 0.8);
+}
+
+#[test]
+fn should_apply_each_operation_cost() {
+    let candidate = "abcd";
+    let costs = EditCosts::default()
+        .insert(0.5)
+        .delete(0.25)
+        .substitute(2.0)
+        .transpose(0.1);
+    let options = FuzzyOptions::default().use_sellers(false).edit_costs(costs);
+
+    // missing "d", so the candidate needs an insertion
+    assert_eq!(fuzzy("abc", candidate, &options), 1.0 - 0.5 / 4.0);
+    // stray "x", so the term needs a deletion
+    assert_eq!(fuzzy("abcxd", candidate, &options), 1.0 - 0.25 / 5.0);
+    // deleting then inserting is cheaper than substituting
+    assert_eq!(fuzzy("abxd", candidate, &options), 1.0 - 0.75 / 4.0);
+    assert_eq!(fuzzy("abdc", candidate, &options), 1.0 - 0.1 / 4.0);
+}
+
+#[test]
+fn should_build_keyboard_tables() {
+    let qwerty = SubstitutionTable::qwerty(0.5);
+    assert_eq!(qwerty.get("e", "r"), Some(0.5));
+    assert_eq!(qwerty.get("r", "e"), Some(0.5));
+    assert_eq!(qwerty.get("e", "d"), Some(0.5));
+    assert_eq!(qwerty.get("e", "3"), Some(0.5));
+    assert_eq!(qwerty.get("g", "b"), Some(0.5));
+    assert_eq!(qwerty.get("e", "q"), None);
+    assert_eq!(qwerty.get("a", "z"), Some(0.5));
+
+    let azerty = SubstitutionTable::azerty(0.5);
+    assert_eq!(azerty.get("a", "z"), Some(0.5));
+    assert_eq!(azerty.get("a", "q"), Some(0.5));
+    assert_eq!(azerty.get("m", "ù"), Some(0.5));
+    assert_eq!(azerty.get("e", "q"), None);
+
+    let dvorak = SubstitutionTable::dvorak(0.5);
+    assert_eq!(dvorak.get("a", "o"), Some(0.5));
+    assert_eq!(dvorak.get("h", "t"), Some(0.5));
+    assert_eq!(dvorak.get("e", "r"), None);
+}
+
+#[test]
+fn should_match_confusable_characters() {
+    let table = SubstitutionTable::confusables(0.1);
+    assert_eq!(table.get("0", "o"), Some(0.1));
+    assert_eq!(table.get("l", "1"), Some(0.1));
+
+    let options = FuzzyOptions::default().edit_costs(EditCosts::default().substitutions(table));
+    assert_gt!(fuzzy("he11o", "hello", &options), 0.95);
+    assert_lt!(fuzzy("he22o", "hello", &options), 0.7);
+}
+
+#[test]
+fn should_keep_the_cheaper_cost_when_merging() {
+    let table = SubstitutionTable::qwerty(0.5)
+        .merge(SubstitutionTable::confusables(0.2))
+        .merge(SubstitutionTable::new().with("o", "p", 0.7));
+
+    assert_eq!(table.get("o", "0"), Some(0.2));
+    assert_eq!(table.get("o", "p"), Some(0.5));
+    assert_eq!(table.get("p", "o"), Some(0.5));
+}
+
+#[test]
+#[should_panic]
+fn should_reject_negative_costs() {
+    let _ = EditCosts::default().insert(-1.0);
+}
+
+#[test]
+fn should_prune_the_trie_without_losing_matches() {
+    // words from a handful of letters, so that plenty of them share
+    // prefixes and fall near the threshold
+    let words = random_strings(7, 400, 1..10, "aehlorb");
+    let cost_sets = [
+        EditCosts::default(),
+        EditCosts::default()
+            .insert(0.3)
+            .delete(0.7)
+            .substitute(0.9)
+            .transpose(0.4),
+        EditCosts::default()
+            .insert(1.5)
+            .delete(0.5)
+            .substitutions(SubstitutionTable::qwerty(0.25)),
+    ];
+
+    for costs in cost_sets {
+        for use_sellers in [true, false] {
+            for use_damerau in [true, false] {
+                let options = || {
+                    FuzzyOptions::default()
+                        .threshold(0.7)
+                        .use_sellers(use_sellers)
+                        .use_damerau(use_damerau)
+                        .edit_costs(costs.clone())
+                };
+                let searcher = Searcher::new(words.clone(), options());
+
+                for term in ["hello", "bear", "loa", "rrabel", "e"] {
+                    let mut expected = words
+                        .iter()
+                        .filter(|word| fuzzy(term, (*word).clone(), &options()) >= 0.7)
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let mut actual = searcher.search(term).unwrap();
+                    expected.sort();
+                    actual.sort();
+
+                    assert_eq!(actual, expected, "{term} with {costs:?}");
+                    assert_eq!(
+                        search(term, words.clone(), &options()).unwrap().len(),
+                        expected.len()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{
+    explain, fuzzy, BoostMode, Edit, EditKind, FuzzyOptions, MatchKind, NumericMatch,
+    PhoneticAlgorithm, Searcher, Synonyms,
+};
+
+fn kinds(alignment: &[Edit]) -> Vec<EditKind> {
+    alignment.iter().map(|edit| edit.kind).collect()
+}
+
+#[test]
+fn should_explain_the_alignment() {
+    let explanation = explain(
+        "hello",
+        "Hallo",
+        &FuzzyOptions::default().use_sellers(false),
+    );
+    assert_eq!(explanation.term, vec!["h", "e", "l", "l", "o"]);
+    assert_eq!(explanation.key, vec!["h", "a", "l", "l", "o"]);
+    assert_eq!(explanation.original, "Hallo");
+    assert_eq!(
+        explanation.alignment[1],
+        Edit {
+            kind: EditKind::Substitute,
+            term: "e".into(),
+            key: "a".into(),
+            cost: 1.0,
+        }
+    );
+    assert_eq!(explanation.distance, 1.0);
+    assert_eq!(explanation.length, 5);
+    assert_eq!(explanation.text_score, 0.8);
+    assert_eq!(explanation.score, 0.8);
+    assert!(explanation.matched);
+}
+
+#[test]
+fn should_explain_every_kind_of_edit() {
+    let options = FuzzyOptions::default().use_sellers(false);
+    let explanation = explain("abcd", "acbd", &options);
+    assert_eq!(
+        kinds(&explanation.alignment),
+        vec![EditKind::Match, EditKind::Transpose, EditKind::Match]
+    );
+    assert_eq!(explanation.alignment[1].term, "bc");
+    assert_eq!(explanation.alignment[1].key, "cb");
+
+    let explanation = explain("helo", "hello", &options);
+    assert!(kinds(&explanation.alignment).contains(&EditKind::Insert));
+    assert_eq!(explanation.length, 5);
+
+    let explanation = explain("helllo", "hello", &options);
+    assert!(kinds(&explanation.alignment).contains(&EditKind::Delete));
+}
+
+#[test]
+fn should_only_align_the_matched_substring_with_sellers() {
+    let explanation = explain("item", "lineitem", &FuzzyOptions::default());
+    assert_eq!(explanation.alignment.len(), 4);
+    assert!(explanation
+        .alignment
+        .iter()
+        .all(|edit| edit.kind == EditKind::Match));
+    assert_eq!(explanation.length, 4);
+    assert_eq!(explanation.score, 1.0);
+}
+
+#[test]
+fn should_agree_with_fuzzy() {
+    let pairs = [
+        ("hello", "help"),
+        ("item", "excitement"),
+        ("street", "st"),
+        ("", "anything"),
+        ("abc", ""),
+    ];
+
+    for (term, key) in pairs {
+        for options in [
+            FuzzyOptions::default(),
+            FuzzyOptions::default().use_sellers(false),
+        ] {
+            let explanation = explain(term, key, &options);
+            assert_eq!(
+                explanation.score,
+                fuzzy(term, key, &options),
+                "{term} {key}"
+            );
+            assert_eq!(
+                explanation.text_score,
+                1.0 - explanation.distance / explanation.length.max(1) as f64
+            );
+        }
+    }
+
+    let phonetic = FuzzyOptions::default().phonetic(PhoneticAlgorithm::Soundex);
+    let explanation = explain("smith", "Smyth", &phonetic);
+    assert_eq!(explanation.phonetic_agreement, Some(1.0));
+    assert_eq!(explanation.score, fuzzy("smith", "Smyth", &phonetic));
+
+    let numeric = FuzzyOptions::default().numeric_tokens(NumericMatch::Exact);
+    let explanation = explain("iphone 13", "iPhone 12", &numeric);
+    assert_eq!(explanation.numbers_match, Some(false));
+    assert_eq!(explanation.score, 0.0);
+    assert!(!explanation.matched);
+}
+
+#[test]
+fn should_not_report_a_text_score_for_paths() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Path);
+
+    let explanation = explain("src/lib", "src/lib.rs", &options);
+    assert_eq!(explanation.text_score, 0.0);
+    assert_eq!(explanation.structured_score, Some(explanation.score));
+    assert_eq!(explanation.score, fuzzy("src/lib", "src/lib.rs", &options));
+
+    // a key that is close as text but not as a path scores nothing
+    let explanation = explain("srclib", "src/lob.rs", &options);
+    assert_eq!(explanation.text_score, 0.0);
+    assert_eq!(explanation.score, fuzzy("srclib", "src/lob.rs", &options));
+    assert_eq!(explanation.score, 0.0);
+}
+
+#[test]
+fn should_explain_boosts_and_expansions() {
+    let searcher = Searcher::new(
+        vec!["Motor mount", "Desk lamp"],
+        FuzzyOptions::default()
+            .boost(|item: &&str| item.len() as f64)
+            .boost_mode(BoostMode::Add),
+    )
+    .synonyms(Synonyms::new().add("mtr", ["motor"]).penalty(0.25))
+    .score_blender(|_: &&str, score: f64| score / 2.0);
+
+    let explanation = searcher.explain("mtr", &"Motor mount");
+    assert_eq!(explanation.boost, Some(11.0));
+    assert_eq!(explanation.expansion.as_ref().unwrap().query, "motor");
+    assert_eq!(explanation.score, (1.0 * 0.75 + 11.0) / 2.0);
+    assert_eq!(explanation.blended, Some(explanation.score));
+
+    let results = searcher.search_data("mtr").unwrap();
+    assert_eq!(results[0].score, explanation.score);
+
+    // nothing is boosted or blended for keys that don't match
+    let explanation = searcher.explain("mtr", &"Desk lamp");
+    assert!(!explanation.matched);
+    assert_eq!(explanation.boost, None);
+    assert_eq!(explanation.blended, None);
+}
+
+#[test]
+fn should_render_a_table() {
+    let text = explain(
+        "helo wrld",
+        "Hello World",
+        &FuzzyOptions::default().use_sellers(false),
+    )
+    .to_string();
+    let lines = text.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], "term        h e l o ␣ w r l d");
+    assert_eq!(lines[1], "key         h e l l o ␣ w o r l d");
+    assert_eq!(lines[2], "original    Hello World (key 0)");
+    assert_eq!(lines[4], "edit        term  key  cost");
+    assert_eq!(lines[5], "match       h     h    0");
+    assert!(lines.contains(&"insert            l    1"));
+    assert!(lines.contains(&"match       ␣     ␣    0"));
+    assert!(text.ends_with("score       0.8181818181818181"));
+}
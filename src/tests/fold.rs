@@ -0,0 +1,133 @@
+use more_asserts::assert_lt;
+
+use crate::{fuzzy, fuzzy_data, search, util::normalize, FuzzyOptions, MatchKind};
+
+#[test]
+fn should_match_regardless_of_accents() {
+    let options = FuzzyOptions::default().strip_diacritics(true);
+    assert_eq!(fuzzy("cafe", "café", &options), 1.0);
+    assert_eq!(fuzzy("crème brûlée", "creme brulee", &options), 1.0);
+    assert_eq!(fuzzy("Ångström", "angstrom", &options), 1.0);
+
+    assert_lt!(fuzzy("cafe", "café", &FuzzyOptions::default()), 1.0);
+}
+
+#[test]
+fn should_fold_letters_with_strokes() {
+    let options = FuzzyOptions::default().strip_diacritics(true);
+    assert_eq!(fuzzy("Koln", "Køln", &options), 1.0);
+    assert_eq!(fuzzy("Lodz", "Łódź", &options), 1.0);
+    assert_eq!(fuzzy("Dakovo", "Đakovo", &options), 1.0);
+}
+
+#[test]
+fn should_keep_grapheme_level_scoring() {
+    // decomposed input still counts as one grapheme per letter
+    let normal = normalize(
+        "cafe\u{301}".to_string(),
+        &FuzzyOptions::<String>::default().strip_diacritics(true),
+    )
+    .normal;
+    assert_eq!(normal, vec!["c", "a", "f", "e"]);
+
+    let normal = normalize(
+        "café".to_string(),
+        &FuzzyOptions::<String>::default()
+            .strip_diacritics(true)
+            .use_separated_unicode(true),
+    )
+    .normal;
+    assert_eq!(normal.len(), 4);
+}
+
+#[test]
+fn should_highlight_the_accented_original() {
+    let data = fuzzy_data(
+        "resume",
+        "my résumé here",
+        &FuzzyOptions::default().strip_diacritics(true),
+    );
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, 3);
+    assert_eq!(data.match_length, "résumé".len());
+}
+
+#[test]
+fn should_strip_diacritics_in_searches() {
+    let candidates = vec!["native", "naïve"];
+    assert_eq!(
+        search(
+            "naive",
+            candidates,
+            &FuzzyOptions::default().strip_diacritics(true)
+        )
+        .unwrap()[0],
+        "naïve"
+    );
+
+    let options = FuzzyOptions::default()
+        .strip_diacritics(true)
+        .match_kind(MatchKind::Identifier);
+    assert_eq!(fuzzy("ns", "naïveSet", &options), 1.0);
+}
+
+#[test]
+fn should_fold_width() {
+    let options = FuzzyOptions::default().fold_width(true);
+    assert_eq!(fuzzy("iphone 15", "ｉＰｈｏｎｅ　１５", &options), 1.0);
+    assert_eq!(fuzzy("カメラ", "ｶﾒﾗ", &options), 1.0);
+    assert_eq!(fuzzy("ガイド", "ｶﾞｲﾄﾞ", &options), 1.0);
+
+    // other compatibility characters are left alone
+    let normal = normalize(
+        "ﬁ①".to_string(),
+        &FuzzyOptions::<String>::default().fold_width(true),
+    );
+    assert_eq!(normal.key(), "ﬁ①");
+
+    assert_lt!(
+        fuzzy("iphone", "ｉＰｈｏｎｅ", &FuzzyOptions::default()),
+        1.0
+    );
+}
+
+#[test]
+fn should_fold_kana() {
+    let options = FuzzyOptions::default().fold_kana(true);
+    assert_eq!(fuzzy("すし", "スシ", &options), 1.0);
+    assert_eq!(fuzzy("コーヒー", "こーひー", &options), 1.0);
+
+    // half-width katakana needs width folding as well
+    let options = options.fold_width(true);
+    assert_eq!(fuzzy("かめら", "ｶﾒﾗ", &options), 1.0);
+
+    assert_lt!(fuzzy("すし", "スシ", &FuzzyOptions::default()), 1.0);
+}
+
+#[test]
+fn should_fold_chinese_variants() {
+    let options = FuzzyOptions::default().fold_chinese_variants(true);
+    assert_eq!(fuzzy("中国", "中國", &options), 1.0);
+    assert_eq!(fuzzy("體育館", "体育馆", &options), 1.0);
+
+    assert_lt!(fuzzy("中国", "中國", &FuzzyOptions::default()), 1.0);
+}
+
+#[test]
+fn should_toggle_folds_independently() {
+    let options = FuzzyOptions::default().fold_kana(true);
+    assert_lt!(fuzzy("abc", "ａｂｃ", &options), 1.0);
+    assert_lt!(fuzzy("中国", "中國", &options), 1.0);
+
+    let options = FuzzyOptions::default().fold_width(true);
+    assert_lt!(fuzzy("すし", "スシ", &options), 1.0);
+}
+
+#[test]
+fn should_highlight_the_unfolded_original() {
+    let options = FuzzyOptions::default().fold_width(true);
+    let data = fuzzy_data("guide", "ｇｕｉｄｅ book", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, 0);
+    assert_eq!(data.match_length, "ｇｕｉｄｅ".len());
+}
@@ -0,0 +1,108 @@
+use std::{
+    fs,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use more_asserts::assert_lt;
+
+use crate::{Frecency, FrecencyError, FuzzyOptions, Searcher};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn palette() -> Searcher<&'static str> {
+    Searcher::new(
+        vec!["open file", "open folder", "open recent"],
+        FuzzyOptions::default(),
+    )
+}
+
+#[test]
+fn should_decay_selections() {
+    let start = SystemTime::UNIX_EPOCH + 1000 * DAY;
+    let frecency = Frecency::new().half_life(DAY);
+    frecency.record_at("open file", start);
+    frecency.record_at("open file", start);
+
+    assert_eq!(frecency.count_at("open file", start), 2.0);
+    assert_eq!(frecency.count_at("open file", start + DAY), 1.0);
+    assert_eq!(frecency.count_at("open file", start + 2 * DAY), 0.5);
+    assert_eq!(frecency.count_at("open folder", start), 0.0);
+
+    // later selections add to what is left of earlier ones
+    frecency.record_at("open file", start + DAY);
+    assert_eq!(frecency.count_at("open file", start + DAY), 2.0);
+}
+
+#[test]
+fn should_rank_used_items_higher() {
+    assert_eq!(palette().search("open").unwrap()[0], "open file");
+
+    let frecency = Arc::new(Frecency::new());
+    let searcher = palette().score_blender(frecency.clone());
+    frecency.record("open recent");
+
+    assert_eq!(
+        searcher.search("open"),
+        Ok(vec!["open recent", "open file", "open folder"])
+    );
+
+    // a much better text match still wins
+    assert_eq!(searcher.search("open file").unwrap()[0], "open file");
+}
+
+#[test]
+fn should_blend_by_weight() {
+    let now = SystemTime::now();
+    let frecency = Frecency::new().weight(0.5);
+    frecency.record_at("open file", now);
+
+    assert_eq!(frecency.blend_at("open file", 1.0, now), 0.75);
+    assert_eq!(frecency.blend_at("open folder", 1.0, now), 0.5);
+
+    let searcher = palette().score_blender(Frecency::new().weight(0.0));
+    let results = searcher.search_data("open").unwrap();
+    assert_eq!(results[0].score, 1.0);
+}
+
+#[test]
+fn should_accept_any_blender() {
+    let searcher = palette().score_blender(|item: &&str, score: f64| {
+        if item.ends_with("folder") {
+            score
+        } else {
+            score / 2.0
+        }
+    });
+
+    let results = searcher.search_data("open").unwrap();
+    assert_eq!(results[0].item, "open folder");
+    assert_lt!(results[1].score, 1.0);
+}
+
+#[test]
+fn should_persist_to_a_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("frecency");
+    let time = SystemTime::UNIX_EPOCH + 1000 * DAY;
+
+    let frecency = Frecency::new();
+    frecency.record_at("open file", time);
+    frecency.record_at("open file", time);
+    frecency.record_at("odd\tkey\nwith \\ escapes", time);
+    frecency.save(&path).unwrap();
+
+    let loaded = Frecency::load(&path).unwrap();
+    assert_eq!(loaded.count_at("open file", time), 2.0);
+    assert_eq!(loaded.count_at("odd\tkey\nwith \\ escapes", time), 1.0);
+
+    fs::write(&path, "not an entry\n").unwrap();
+    assert!(matches!(
+        Frecency::load(&path),
+        Err(FrecencyError::Parse(1))
+    ));
+    assert!(matches!(
+        Frecency::load(dir.path().join("missing")),
+        Err(FrecencyError::Io(_))
+    ));
+}
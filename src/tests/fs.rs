@@ -0,0 +1,147 @@
+use std::{fs, path::PathBuf};
+
+use tempfile::TempDir;
+
+use crate::{FileSearchOptions, FileSearcher, FuzzyOptions, MatchKind};
+
+fn tree(files: &[&str]) -> TempDir {
+    let root = tempfile::tempdir().unwrap();
+    for file in files {
+        let path = root.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+    root
+}
+
+fn paths(paths: &[&str]) -> Vec<PathBuf> {
+    paths.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn should_index_relative_paths() {
+    let root = tree(&["src/lib.rs", "src/util.rs", "README.md"]);
+    let searcher = FileSearcher::new(root.path(), FileSearchOptions::default()).unwrap();
+
+    assert_eq!(searcher.len(), 3);
+    assert_eq!(searcher.search("src/ut"), Ok(paths(&["src/util.rs"])));
+
+    let data = searcher.search_data("util").unwrap();
+    assert_eq!(data[0].item, PathBuf::from("src/util.rs"));
+    assert_eq!(data[0].spans, vec![(4, 5)]);
+}
+
+#[test]
+fn should_honor_ignore_files() {
+    let root = tree(&[
+        ".gitignore",
+        ".ignore",
+        "src/lib.rs",
+        "target/debug/lib.rs",
+        "generated/lib.rs",
+    ]);
+    fs::write(root.path().join(".gitignore"), "target/\n").unwrap();
+    fs::write(root.path().join(".ignore"), "generated/\n").unwrap();
+
+    let searcher = FileSearcher::new(root.path(), FileSearchOptions::default()).unwrap();
+    assert_eq!(searcher.search("lib"), Ok(paths(&["src/lib.rs"])));
+
+    let searcher = FileSearcher::new(
+        root.path(),
+        FileSearchOptions::default().ignore_files(false),
+    )
+    .unwrap();
+    assert_eq!(searcher.search("lib").unwrap().len(), 3);
+}
+
+#[test]
+fn should_skip_hidden_files_unless_asked() {
+    let root = tree(&[".config/settings.toml", "settings.toml"]);
+
+    let searcher = FileSearcher::new(root.path(), FileSearchOptions::default()).unwrap();
+    assert_eq!(searcher.search("settings"), Ok(paths(&["settings.toml"])));
+
+    let searcher =
+        FileSearcher::new(root.path(), FileSearchOptions::default().hidden(true)).unwrap();
+    assert_eq!(
+        searcher.search("settings"),
+        Ok(paths(&["settings.toml", ".config/settings.toml"]))
+    );
+}
+
+#[test]
+fn should_apply_include_and_exclude_globs() {
+    let root = tree(&[
+        "src/main.rs",
+        "src/main.js",
+        "vendor/lib/main.rs",
+        "docs/main.md",
+    ]);
+
+    let searcher = FileSearcher::new(
+        root.path(),
+        FileSearchOptions::default()
+            .include("*.rs")
+            .exclude("vendor"),
+    )
+    .unwrap();
+    assert_eq!(searcher.search("main"), Ok(paths(&["src/main.rs"])));
+
+    assert!(FileSearcher::new(root.path(), FileSearchOptions::default().include("a{")).is_err());
+}
+
+#[test]
+fn should_pick_up_changes_on_rescan() {
+    let root = tree(&["old.txt"]);
+    let mut searcher = FileSearcher::new(root.path(), FileSearchOptions::default()).unwrap();
+    assert_eq!(searcher.search("new"), Ok(vec![]));
+
+    fs::write(root.path().join("new.txt"), "").unwrap();
+    fs::remove_file(root.path().join("old.txt")).unwrap();
+    searcher.rescan().unwrap();
+
+    assert_eq!(searcher.len(), 1);
+    assert_eq!(searcher.search("new"), Ok(paths(&["new.txt"])));
+    assert_eq!(searcher.search("old"), Ok(vec![]));
+}
+
+#[test]
+fn should_refresh_after_removing_most_files() {
+    let files = (0..100)
+        .map(|i| format!("build/{i}.o"))
+        .chain(["keep.txt".to_string()])
+        .collect::<Vec<_>>();
+    let root = tree(&files.iter().map(String::as_str).collect::<Vec<_>>());
+    let mut searcher = FileSearcher::new(root.path(), FileSearchOptions::default()).unwrap();
+
+    fs::remove_dir_all(root.path().join("build")).unwrap();
+    searcher.refresh(["build"]).unwrap();
+    assert_eq!(searcher.len(), 1);
+    assert_eq!(searcher.search("keep"), Ok(paths(&["keep.txt"])));
+
+    fs::write(root.path().join("kept.txt"), "").unwrap();
+    fs::remove_file(root.path().join("keep.txt")).unwrap();
+    searcher.refresh(["keep.txt", "kept.txt"]).unwrap();
+    assert_eq!(searcher.len(), 1);
+    assert_eq!(searcher.search("kept"), Ok(paths(&["kept.txt"])));
+}
+
+#[test]
+fn should_accept_custom_fuzzy_options() {
+    let root = tree(&["HttpRequestContext.java"]);
+    let searcher = FileSearcher::new(
+        root.path(),
+        FileSearchOptions::default().fuzzy_options(
+            FuzzyOptions::default_with_key_selector(|path: &PathBuf| {
+                vec![path.to_string_lossy().into_owned()]
+            })
+            .match_kind(MatchKind::Identifier),
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(
+        searcher.search("HRC"),
+        Ok(paths(&["HttpRequestContext.java"]))
+    );
+}
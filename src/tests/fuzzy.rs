@@ -3,19 +3,16 @@ use more_asserts::{assert_ge, assert_lt};
 
 #[test]
 fn should_score_exact_matches_perfectly() {
-    assert_eq!(fuzzy("hello", "hello", &FuzzyOptions::default()), 10.0);
-    assert_eq!(fuzzy("goodbye", "goodbye", &FuzzyOptions::default()), 10.0);
+    assert_eq!(fuzzy("hello", "hello", &FuzzyOptions::default()), 1.0);
+    assert_eq!(fuzzy("goodbye", "goodbye", &FuzzyOptions::default()), 1.0);
 }
 
 #[test]
 fn should_score_exact_substring_matches_perfectly() {
-    assert_eq!(
-        fuzzy("hello", "hello there", &FuzzyOptions::default()),
-        10.0
-    );
+    assert_eq!(fuzzy("hello", "hello there", &FuzzyOptions::default()), 1.0);
     assert_eq!(
         fuzzy("goodbye", "well, goodbye then", &FuzzyOptions::default()),
-        10.0
+        1.0
     );
 }
 
@@ -45,7 +42,7 @@ fn should_score_non_matches_minimally() {
 
 #[test]
 fn should_return_perfect_scores_for_empty_search_terms() {
-    assert_eq!(fuzzy("", "anything", &FuzzyOptions::default()), 10.0);
+    assert_eq!(fuzzy("", "anything", &FuzzyOptions::default()), 1.0);
 }
 
 #[test]
@@ -58,7 +55,7 @@ fn should_handle_unicode_well() {
     // unicode characters are normalized
     assert_eq!(
         fuzzy("\u{212B}", "\u{0041}\u{030A}", &FuzzyOptions::default()),
-        10.0
+        1.0
     );
     // handles high and low surrogates as single characters
     assert_eq!(fuzzy("high", "h💩gh", &FuzzyOptions::default()), 0.75);
@@ -80,9 +77,9 @@ fn should_handle_unicode_well_with_use_separated_unicode() {
     let options = FuzzyOptions::default().use_separated_unicode(true);
 
     // unicode characters are normalized
-    assert_eq!(fuzzy("\u{212B}", "\u{0041}\u{030A}", &options), 10.0);
-    // handles high and low surrogates as multiple characters
-    assert_eq!(fuzzy("high", "h💩gh", &options), 0.5);
+    assert_eq!(fuzzy("\u{212B}", "\u{0041}\u{030A}", &options), 1.0);
+    // chars are unicode scalar values, so there are no surrogates to split
+    assert_eq!(fuzzy("high", "h💩gh", &options), 0.75);
     // handles combining marks as single characters
     assert_eq!(
         fuzzy("hi zalgo hello hello", "hi Z͑ͫ̓ͪ̂ͫ̽͏̴̙̤̞͉͚̯̞̠͍A̴̵̜̰͔ͫ͗͢L̠ͨͧͩ͘G̴̻͈͍͔̹̑͗̎̅͛́Ǫ̵̹̻̝̳͂̌̌͘ hello hello", &options),
@@ -100,10 +97,13 @@ mod options {
     use crate::{fuzzy, fuzzy_data, FuzzyOptions, MatchData};
     #[test]
     fn should_have_different_results_when_ignore_case_is_set() {
-        let options = FuzzyOptions::default().ignore_case(true);
         assert_gt!(
-            fuzzy("hello", "HELLO", &options),
-            fuzzy("hello", "HELLO", &options),
+            fuzzy("hello", "HELLO", &FuzzyOptions::default().ignore_case(true)),
+            fuzzy(
+                "hello",
+                "HELLO",
+                &FuzzyOptions::default().ignore_case(false)
+            ),
         );
     }
     #[test]
@@ -157,7 +157,10 @@ mod options {
                 key: "acbd".into(),
                 score: 0.75,
                 match_index: 0,
-                match_length: 5,
+                match_length: 4,
+                spans: vec![(0, 4)],
+                expansion: None,
+                boost: None,
             }
         );
     }
@@ -172,6 +175,9 @@ mod options {
                 score: 0.8,
                 match_index: 2,
                 match_length: 10,
+                spans: vec![(2, 10)],
+                expansion: None,
+                boost: None,
             }
         );
     }
@@ -0,0 +1,137 @@
+use more_asserts::{assert_ge, assert_gt, assert_lt};
+
+use crate::{
+    fuzzy, fuzzy_data, identifier::split_words, search, FuzzyOptions, Language, MatchKind,
+    Pipeline, Searcher, Segment, StopWords,
+};
+
+fn words(key: &str) -> Vec<&str> {
+    split_words(key)
+        .into_iter()
+        .map(|(index, length)| &key[index..index + length])
+        .collect()
+}
+
+#[test]
+fn should_split_identifiers_into_words() {
+    assert_eq!(words("get_config_file"), ["get", "config", "file"]);
+    assert_eq!(words("HttpRequestContext"), ["Http", "Request", "Context"]);
+    assert_eq!(words("HTTPRequest"), ["HTTP", "Request"]);
+    assert_eq!(words("parseJSON"), ["parse", "JSON"]);
+    assert_eq!(words("kebab-case-name"), ["kebab", "case", "name"]);
+    assert_eq!(words("src/util.rs"), ["src", "util", "rs"]);
+    assert_eq!(words("std::io::Read"), ["std", "io", "Read"]);
+    assert_eq!(words("utf8Decoder"), ["utf", "8", "Decoder"]);
+    assert_eq!(words("__private"), ["private"]);
+}
+
+#[test]
+fn should_match_initials_and_subsequences() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Identifier);
+
+    assert_ge!(fuzzy("gcfg", "get_config_file", &options), 0.6);
+    assert_eq!(fuzzy("HRC", "HttpRequestContext", &options), 1.0);
+    assert_eq!(fuzzy("hrc", "http-request-context", &options), 1.0);
+    assert_eq!(fuzzy("gcf", "get_config_file", &options), 1.0);
+
+    // these aren't close enough in plain edit distance
+    let fuzzy_options = FuzzyOptions::default();
+    assert_lt!(fuzzy("HRC", "HttpRequestContext", &fuzzy_options), 0.6);
+}
+
+#[test]
+fn should_reward_word_boundaries() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Identifier);
+
+    assert_gt!(
+        fuzzy("gcf", "get_config_file", &options),
+        fuzzy("gcf", "gecfile", &options),
+    );
+    assert_gt!(
+        fuzzy("conf", "get_config_file", &options),
+        fuzzy("conf", "get_cxoxnxfig", &options),
+    );
+}
+
+#[test]
+fn should_fall_back_to_fuzzy_matching() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Identifier);
+
+    assert_eq!(
+        fuzzy("confgi", "get_config_file", &options),
+        fuzzy("confgi", "get_config_file", &FuzzyOptions::default()),
+    );
+}
+
+#[test]
+fn should_return_every_matched_span() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Identifier);
+
+    let data = fuzzy_data("HRC", "HttpRequestContext", &options);
+    assert_eq!(data.spans, vec![(0, 1), (4, 1), (11, 1)]);
+    assert_eq!(data.match_index, 0);
+    assert_eq!(data.match_length, 12);
+
+    let data = fuzzy_data("getcon", "get_config_file", &options);
+    assert_eq!(data.spans, vec![(0, 3), (4, 3)]);
+}
+
+#[test]
+fn should_find_identifiers_when_searching() {
+    let candidates = vec![
+        "get_config_file",
+        "HttpRequestContext",
+        "GraphicsConfig",
+        "set_config_file",
+        "unrelated",
+    ];
+    let options = FuzzyOptions::default().match_kind(MatchKind::Identifier);
+
+    assert_eq!(
+        search("gcfg", candidates.clone(), &options),
+        Ok(vec!["get_config_file", "GraphicsConfig"])
+    );
+    assert_eq!(
+        Searcher::new(candidates, options).search("HRC"),
+        Ok(vec!["HttpRequestContext", "GraphicsConfig"])
+    );
+}
+
+#[test]
+fn should_normalize_with_the_pipeline() {
+    let options = FuzzyOptions::<&str>::default().match_kind(MatchKind::Identifier);
+    let eszett = Pipeline::new()
+        .then(|segments: Vec<Segment>| {
+            segments
+                .into_iter()
+                .map(|segment| match segment.text.as_str() {
+                    "ß" => Segment::new("x", segment.offset),
+                    _ => segment,
+                })
+                .collect()
+        })
+        .chain(Pipeline::from_options(&options));
+    let custom = FuzzyOptions::default()
+        .match_kind(MatchKind::Identifier)
+        .pipeline(eszett);
+
+    let data = fuzzy_data("gxc", "get_ßomething_config", &custom);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.spans, vec![(0, 1), (4, "ß".len()), (15, 1)]);
+}
+
+#[test]
+fn should_drop_stop_words_from_identifiers() {
+    let english = FuzzyOptions::default()
+        .match_kind(MatchKind::Identifier)
+        .stop_words(StopWords::language(Language::English));
+    assert_eq!(
+        search("the gc", vec!["get_config", "zebra"], &english),
+        Ok(vec!["get_config"])
+    );
+
+    let short = FuzzyOptions::default()
+        .match_kind(MatchKind::Identifier)
+        .min_token_length(2);
+    assert_eq!(fuzzy("x gc", "get_config", &short), 1.0);
+}
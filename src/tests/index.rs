@@ -0,0 +1,108 @@
+use super::{assert_same_as_brute_force, phrases, TERMS};
+use crate::{
+    BoostMode, BruteForceIndex, FuzzyOptions, IndexKind, MatchKind, PhoneticAlgorithm, QGramIndex,
+    Searcher, SymSpellIndex, Synonyms,
+};
+
+type NewIndex = fn() -> IndexKind;
+
+#[test]
+fn should_find_the_same_matches_as_brute_force() {
+    let indices: [NewIndex; 4] = [
+        || IndexKind::Trie,
+        || IndexKind::SymSpell(SymSpellIndex::new()),
+        || IndexKind::QGram(QGramIndex::new()),
+        || IndexKind::BkTree,
+    ];
+
+    for index in indices {
+        for threshold in [0.0, 0.5, 0.8] {
+            let options = move || FuzzyOptions::default().threshold(threshold);
+            assert_same_as_brute_force(options, index().into_index());
+            assert_same_as_brute_force(move || options().use_sellers(false), index().into_index());
+            assert_same_as_brute_force(move || options().use_damerau(false), index().into_index());
+        }
+
+        assert_same_as_brute_force(
+            || {
+                FuzzyOptions::default()
+                    .threshold(0.6)
+                    .phonetic(PhoneticAlgorithm::Soundex)
+            },
+            index().into_index(),
+        );
+        assert_same_as_brute_force(
+            || {
+                FuzzyOptions::default()
+                    .threshold(0.6)
+                    .match_kind(MatchKind::Identifier)
+            },
+            index().into_index(),
+        );
+        assert_same_as_brute_force(
+            || {
+                FuzzyOptions::default()
+                    .threshold(0.6)
+                    .boost(|phrase: &String| phrase.len() as f64)
+                    .boost_mode(BoostMode::Add)
+            },
+            index().into_index(),
+        );
+    }
+}
+
+#[test]
+fn should_take_the_first_k_results() {
+    let searcher = Searcher::new(phrases(), FuzzyOptions::default().threshold(0.5));
+    let brute_force = Searcher::with_index(
+        phrases(),
+        FuzzyOptions::default().threshold(0.5),
+        BruteForceIndex::new(),
+    );
+
+    for term in TERMS {
+        let mut expected = searcher.search(term).unwrap();
+        expected.truncate(5);
+        assert_eq!(searcher.search_top_k(term, 5), Ok(expected.clone()));
+        assert_eq!(brute_force.search_top_k(term, 5), Ok(expected));
+    }
+}
+
+#[test]
+fn should_add_and_remove_candidates() {
+    let mut searcher = Searcher::with_index_kind(
+        vec!["hello world", "yellow world", "goodbye"],
+        FuzzyOptions::default(),
+        IndexKind::BruteForce,
+    );
+    assert_eq!(
+        searcher.search("hello"),
+        Ok(vec!["hello world", "yellow world"])
+    );
+
+    assert_eq!(searcher.remove_where(|phrase| phrase.starts_with('h')), 1);
+    assert_eq!(searcher.search("hello"), Ok(vec!["yellow world"]));
+
+    searcher.add(vec!["jello"]);
+    assert_eq!(searcher.search("hello"), Ok(vec!["jello", "yellow world"]));
+    assert_eq!(searcher.suggest("jelo", 1)[0].text, "jello");
+
+    searcher.clear();
+    assert_eq!(searcher.search("hello"), Ok(vec![]));
+}
+
+#[test]
+fn should_move_to_another_kind_of_index() {
+    let mut searcher = Searcher::new(
+        vec!["hello world", "yellow world", "goodbye"],
+        FuzzyOptions::default().use_sellers(false).threshold(0.5),
+    )
+    .synonyms(Synonyms::new().add("bye", ["goodbye"]));
+    searcher.remove_where(|phrase| phrase.starts_with('y'));
+
+    let searcher = searcher.index_kind(IndexKind::BkTree);
+    assert_eq!(searcher.len(), 2);
+    assert_eq!(searcher.search("hello wrld"), Ok(vec!["hello world"]));
+    assert_eq!(searcher.search("yellow world"), Ok(vec!["hello world"]));
+    assert_eq!(searcher.search("bye"), Ok(vec!["goodbye"]));
+}
@@ -1,3 +1,88 @@
+mod bktree;
+mod boost;
+mod case;
+mod confusable;
+mod costs;
+mod explain;
+mod fold;
+mod frecency;
+#[cfg(feature = "fs")]
+mod fs;
 mod fuzzy;
+mod identifier;
+mod index;
+mod normalizer;
+mod numeric;
+mod path;
+mod phonetic;
+mod qgram;
 mod search;
 mod searcher;
+mod sort;
+mod stop_words;
+mod suggest;
+mod symspell;
+mod synonyms;
+mod transliterate;
+#[cfg(feature = "watch")]
+mod watch;
+
+use std::ops::Range;
+
+use crate::{BruteForceIndex, FuzzyOptions, Index, Searcher};
+
+// `count` fixed pseudo-random strings with lengths in `lengths`, drawn from
+// `alphabet`
+fn random_strings(seed: u32, count: usize, lengths: Range<u32>, alphabet: &str) -> Vec<String> {
+    let alphabet = alphabet.chars().collect::<Vec<_>>();
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+
+    (0..count)
+        .map(|_| {
+            let length = lengths.start + next() % (lengths.end - lengths.start);
+            (0..length)
+                .map(|_| alphabet[next() as usize % alphabet.len()])
+                .collect()
+        })
+        .collect()
+}
+
+// a few hundred phrases over a small alphabet, so that many of them nearly
+// match
+fn phrases() -> Vec<String> {
+    random_strings(0x1234_5678, 300, 1..13, " bcdefg")
+}
+
+const TERMS: [&str; 8] = [
+    "",
+    "b",
+    "bcd",
+    "dcba",
+    "bbccddee",
+    "gfedcbbcdefg",
+    "b c d e",
+    "zzzzzz",
+];
+
+// the backends are tested against the one that shares none of their pruning
+fn assert_same_as_brute_force(
+    options: impl Fn() -> FuzzyOptions<String>,
+    index: impl Index<String>,
+) {
+    let brute_force = Searcher::with_index(phrases(), options(), BruteForceIndex::new());
+    let searcher = Searcher::with_index(phrases(), options(), index);
+
+    for term in TERMS {
+        assert_eq!(
+            searcher.search_data(term),
+            brute_force.search_data(term),
+            "{term}"
+        );
+    }
+}
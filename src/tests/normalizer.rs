@@ -0,0 +1,99 @@
+use crate::{
+    fuzzy, fuzzy_data, search, util::normalize, CaseMode, ChangeCase, CollapseWhitespace,
+    FuzzyOptions, Pipeline, RemoveSymbols, Segment, SplitGraphemes,
+};
+
+fn ampersands(segments: Vec<Segment>) -> Vec<Segment> {
+    segments
+        .into_iter()
+        .map(|segment| match segment.text.as_str() {
+            "&" => Segment::new("and", segment.offset),
+            _ => segment,
+        })
+        .collect()
+}
+
+// Drops a `SKU-` prefix, as in `SKU-1234`.
+fn sku_prefix(segments: Vec<Segment>) -> Vec<Segment> {
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<String>();
+
+    if text.starts_with("sku-") {
+        segments.into_iter().skip(4).collect()
+    } else {
+        segments
+    }
+}
+
+fn with_stages<T: ToString>(stages: Pipeline) -> FuzzyOptions<T> {
+    let options = FuzzyOptions::default();
+    let pipeline = stages.chain(Pipeline::from_options(&options));
+    options.pipeline(pipeline)
+}
+
+#[test]
+fn should_build_the_default_pipeline_from_the_flags() {
+    let flags = FuzzyOptions::<String>::default().strip_diacritics(true);
+    let pipeline = flags
+        .as_str_options()
+        .strip_diacritics(false)
+        .pipeline(Pipeline::from_options(&flags));
+
+    let normalized = normalize("  Crème  Brûlée! ".to_string(), &pipeline);
+    assert_eq!(normalized.key(), "creme brulee");
+    assert_eq!(normalized.map.first(), Some(&2));
+    assert_eq!(normalized.map.last(), Some(&"  Crème  Brûlée".len()));
+
+    let normalized = normalize("straße".to_string(), &pipeline);
+    assert_eq!(normalized.key(), "straße");
+}
+
+#[test]
+fn should_run_custom_stages() {
+    let options = with_stages(Pipeline::new().then(ampersands));
+    assert_eq!(fuzzy("salt and pepper", "Salt & Pepper", &options), 1.0);
+    assert!(fuzzy("salt and pepper", "Salt & Pepper", &FuzzyOptions::default()) < 1.0);
+}
+
+#[test]
+fn should_highlight_expanded_segments_whole() {
+    let options = with_stages(Pipeline::new().then(ampersands));
+    let data = fuzzy_data("an", "Salt & Pepper", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, 5);
+    assert_eq!(data.match_length, 1);
+}
+
+#[test]
+fn should_highlight_after_dropped_segments() {
+    let options = FuzzyOptions::default().pipeline(
+        Pipeline::new()
+            .then(ChangeCase(CaseMode::Lower))
+            .then(sku_prefix)
+            .then(SplitGraphemes),
+    );
+    let data = fuzzy_data("1234", "SKU-1234", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, 4);
+    assert_eq!(data.match_length, 4);
+
+    assert_eq!(
+        search(
+            "sku",
+            vec!["SKU-1234".to_string(), "SKU-9912".to_string()],
+            &options.threshold(1.0)
+        ),
+        Ok(vec![]),
+    );
+}
+
+#[test]
+fn should_only_run_the_given_stages() {
+    // without a case stage, case matters
+    let options = FuzzyOptions::default()
+        .pipeline(Pipeline::new().then(CollapseWhitespace).then(RemoveSymbols));
+    assert_eq!(fuzzy("abc", "ABC", &options), 0.0);
+    assert_eq!(fuzzy("a b", " a   b! ", &options), 1.0);
+}
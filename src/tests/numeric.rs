@@ -0,0 +1,84 @@
+use more_asserts::assert_gt;
+
+use crate::{fuzzy, fuzzy_data, search, util::normalize, FuzzyOptions, NumericMatch};
+
+#[test]
+fn should_only_match_equal_numbers() {
+    let exact = FuzzyOptions::default().numeric_tokens(NumericMatch::Exact);
+    assert_eq!(fuzzy("iphone 13", "iPhone 12", &exact), 0.0);
+    assert_eq!(fuzzy("iphone 13", "iPhone 130", &exact), 0.0);
+    assert_eq!(fuzzy("iphone 13", "Apple iPhone 13 Pro", &exact), 1.0);
+
+    assert_gt!(
+        fuzzy("iphone 13", "iPhone 12", &FuzzyOptions::default()),
+        0.8
+    );
+}
+
+#[test]
+fn should_search_with_numbers() {
+    assert_eq!(
+        search(
+            "iphone 13",
+            vec!["iPhone 12", "iPhone 13 mini", "iPhone 13", "iPhone 3"],
+            &FuzzyOptions::default().numeric_tokens(NumericMatch::Exact)
+        ),
+        Ok(vec!["iPhone 13", "iPhone 13 mini"])
+    );
+}
+
+#[test]
+fn should_score_numbers_as_units() {
+    let exact = FuzzyOptions::default().numeric_tokens(NumericMatch::Exact);
+    let normalized = normalize("v1.25 beta".to_string(), &exact);
+    assert_eq!(
+        normalized.normal,
+        vec!["v", "1.25", " ", "b", "e", "t", "a"]
+    );
+
+    // the term is seven units long, and misses one grapheme of the key
+    assert_eq!(fuzzy("ipone 13", "iphone 13", &exact), 1.0 - 1.0 / 7.0);
+}
+
+#[test]
+fn should_compare_decimals_by_value() {
+    let exact = FuzzyOptions::default().numeric_tokens(NumericMatch::Exact);
+    assert_eq!(fuzzy("1.5 kg", "1.50 kg", &exact), 1.0);
+    assert_eq!(fuzzy("1.5 kg", "15 kg", &exact), 0.0);
+}
+
+#[test]
+fn should_read_digits_of_any_script() {
+    let exact = FuzzyOptions::default().numeric_tokens(NumericMatch::Exact);
+    assert_eq!(fuzzy("iphone 13", "iPhone １３", &exact), 1.0);
+    assert_eq!(fuzzy("13", "١٣", &exact), 1.0);
+    assert_eq!(fuzzy("13", "१२", &exact), 0.0);
+}
+
+#[test]
+fn should_match_number_prefixes() {
+    let prefix = FuzzyOptions::default().numeric_tokens(NumericMatch::Prefix);
+    assert_eq!(fuzzy("model 13", "Model 1350", &prefix), 1.0);
+    assert_eq!(fuzzy("model 13", "Model 1250", &prefix), 0.0);
+}
+
+#[test]
+fn should_match_numbers_within_tolerance() {
+    let tolerance = FuzzyOptions::default().numeric_tokens(NumericMatch::Tolerance(1.0));
+    assert_eq!(fuzzy("size 10", "Size 11", &tolerance), 1.0);
+    assert_eq!(fuzzy("size 10", "Size 9", &tolerance), 1.0);
+    assert_eq!(fuzzy("size 10", "Size 12", &tolerance), 0.0);
+}
+
+#[test]
+fn should_highlight_whole_numbers() {
+    let exact = FuzzyOptions::default().numeric_tokens(NumericMatch::Exact);
+    let data = fuzzy_data("13", "iPhone 13 Pro", &exact);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, 7);
+    assert_eq!(data.match_length, 2);
+
+    let data = fuzzy_data("1.5", "Cable 1.5 m", &exact);
+    assert_eq!(data.match_index, 6);
+    assert_eq!(data.match_length, 3);
+}
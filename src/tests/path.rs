@@ -0,0 +1,121 @@
+use more_asserts::assert_gt;
+
+use crate::{fuzzy, fuzzy_data, search, FuzzyOptions, MatchKind, Searcher};
+
+fn tree() -> Vec<&'static str> {
+    vec![
+        "Cargo.toml",
+        "README.md",
+        "src/lib.rs",
+        "src/util.rs",
+        "src/utils/mod.rs",
+        "src/options.rs",
+        "src/searcher.rs",
+        "src/tests/mod.rs",
+        "src/tests/searcher.rs",
+        "src/tests/util.rs",
+        "benches/search.rs",
+        "crates/cli/src/main.rs",
+        "crates/cli/src/output/table.rs",
+        "crates/core/src/lib.rs",
+        "crates/core/src/config/loader.rs",
+        "docs/utilities.md",
+        "web/node_modules/react/index.js",
+        "web/src/components/Button.tsx",
+        "web/src/components/button/styles.css",
+    ]
+}
+
+#[test]
+fn should_match_segment_by_segment() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Path);
+    assert_eq!(fuzzy("src/ut", "src/util.rs", &options), 1.0);
+    assert_eq!(fuzzy("src/ut", "lib/util.rs", &options), 0.0);
+    assert_eq!(fuzzy("src/util.rs", "src/util.rs", &options), 1.0);
+    // segments have to be next to each other without `**`
+    assert_eq!(
+        fuzzy("crates/main", "crates/cli/src/main.rs", &options),
+        0.0
+    );
+}
+
+#[test]
+fn should_skip_segments_with_double_stars() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Path);
+    assert_gt!(
+        fuzzy("crates/**/main", "crates/cli/src/main.rs", &options),
+        0.6
+    );
+    assert_gt!(
+        fuzzy("**/loader", "crates/core/src/config/loader.rs", &options),
+        0.6
+    );
+    assert_eq!(
+        search("web/**/button", tree(), &options),
+        Ok(vec![
+            "web/src/components/Button.tsx",
+            "web/src/components/button/styles.css",
+        ])
+    );
+}
+
+#[test]
+fn should_prefer_file_name_matches() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Path);
+    assert_gt!(
+        fuzzy("util", "src/util.rs", &options),
+        fuzzy("util", "src/util/mod.rs", &options),
+    );
+    assert_eq!(
+        search("util", tree(), &options),
+        Ok(vec![
+            "src/util.rs",
+            "docs/utilities.md",
+            "src/tests/util.rs",
+            "src/utils/mod.rs",
+        ])
+    );
+}
+
+#[test]
+fn should_penalize_depth() {
+    let options = FuzzyOptions::default().match_kind(MatchKind::Path);
+    assert_eq!(
+        search("lib.rs", tree(), &options),
+        Ok(vec!["src/lib.rs", "crates/core/src/lib.rs"])
+    );
+    assert_eq!(
+        search("src/lib", tree(), &options),
+        Ok(vec!["src/lib.rs", "crates/core/src/lib.rs"])
+    );
+}
+
+#[test]
+fn should_highlight_each_segment() {
+    let options = FuzzyOptions::<&str>::default().match_kind(MatchKind::Path);
+    let data = fuzzy_data("src/ut", "src/util.rs", &options.as_str_options());
+    assert_eq!(data.spans, vec![(0, 3), (4, 2)]);
+    assert_eq!(data.match_index, 0);
+    assert_eq!(data.match_length, 6);
+
+    let data = fuzzy_data(
+        "crates/**/loader",
+        "crates/core/src/config/loader.rs",
+        &options.as_str_options(),
+    );
+    assert_eq!(data.spans, vec![(0, 6), (23, 7)]);
+}
+
+#[test]
+fn should_search_with_a_searcher() {
+    let searcher = Searcher::new(tree(), FuzzyOptions::default().match_kind(MatchKind::Path));
+    assert_eq!(
+        searcher.search("tests/searcher"),
+        Ok(vec!["src/tests/searcher.rs"])
+    );
+    assert_eq!(searcher.search("cli/table"), Ok(vec![]));
+    assert_eq!(
+        searcher.search("cli/**/table"),
+        Ok(vec!["crates/cli/src/output/table.rs"])
+    );
+}
@@ -0,0 +1,120 @@
+use more_asserts::{assert_ge, assert_gt, assert_lt};
+
+use crate::{
+    fuzzy,
+    phonetic::{cologne, double_metaphone, metaphone, soundex},
+    search, FuzzyOptions, PhoneticAlgorithm, Searcher,
+};
+
+fn double(primary: &str, alternate: &str) -> (String, String) {
+    (primary.into(), alternate.into())
+}
+
+#[test]
+fn should_encode_soundex() {
+    assert_eq!(soundex("Robert"), "R163");
+    assert_eq!(soundex("Rupert"), "R163");
+    assert_eq!(soundex("Rubin"), "R150");
+    assert_eq!(soundex("Ashcraft"), "A261");
+    assert_eq!(soundex("Tymczak"), "T522");
+    assert_eq!(soundex("Pfister"), "P236");
+    assert_eq!(soundex("Honeyman"), "H555");
+    assert_eq!(soundex("42"), "");
+}
+
+#[test]
+fn should_encode_metaphone() {
+    assert_eq!(metaphone("Knight"), "NT");
+    assert_eq!(metaphone("Smith"), "SM0");
+    assert_eq!(metaphone("Catherine"), "K0RN");
+    assert_eq!(metaphone("Kathryn"), "K0RN");
+    assert_eq!(metaphone("Philip"), "FLP");
+    assert_eq!(metaphone("Wright"), "RT");
+    assert_eq!(metaphone("Xavier"), "SFR");
+    assert_eq!(metaphone("Dumb"), "TM");
+}
+
+#[test]
+fn should_encode_double_metaphone() {
+    assert_eq!(double_metaphone("Smith"), double("SM0", "XMT"));
+    assert_eq!(double_metaphone("Schmidt"), double("XMT", "SMT"));
+    assert_eq!(double_metaphone("Jose"), double("HS", "HS"));
+    assert_eq!(double_metaphone("Xavier"), double("SF", "SFR"));
+    assert_eq!(double_metaphone("Gallegos"), double("KLKS", "KKS"));
+    assert_eq!(double_metaphone("Filipowicz"), double("FLPT", "FLPF"));
+    assert_eq!(double_metaphone("Michael"), double("MKL", "MXL"));
+    assert_eq!(double_metaphone("Caesar"), double("SSR", "SSR"));
+    assert_eq!(double_metaphone("Laugh"), double("LF", "LF"));
+    assert_eq!(double_metaphone("Island"), double("ALNT", "ALNT"));
+}
+
+#[test]
+fn should_encode_cologne() {
+    assert_eq!(cologne("Müller"), "657");
+    assert_eq!(cologne("Mueller"), "657");
+    assert_eq!(cologne("Meyer"), cologne("Maier"));
+    assert_eq!(cologne("Müller-Lüdenscheidt"), "65752682");
+    assert_eq!(cologne("Wikipedia"), "3412");
+}
+
+#[test]
+fn should_match_names_that_sound_alike() {
+    let options = FuzzyOptions::default().phonetic(PhoneticAlgorithm::DoubleMetaphone);
+    assert_ge!(fuzzy("Schmidt", "Smith", &options), 0.6);
+    assert_ge!(fuzzy("Catherine", "Kathryn", &options), 0.6);
+
+    let options = FuzzyOptions::default().phonetic(PhoneticAlgorithm::Cologne);
+    assert_ge!(fuzzy("Maier", "Meyer", &options), 0.6);
+
+    // without phonetic agreement, the same edit distance scores lower
+    let options = FuzzyOptions::default().phonetic(PhoneticAlgorithm::Metaphone);
+    assert_gt!(
+        fuzzy("Catherine", "Kathryn", &options),
+        fuzzy("Catherine", "Katerina", &options)
+    );
+}
+
+#[test]
+fn should_weigh_phonetic_agreement() {
+    let unweighted = fuzzy("Schmidt", "Smith", &FuzzyOptions::default());
+    let options = FuzzyOptions::default()
+        .phonetic(PhoneticAlgorithm::DoubleMetaphone)
+        .phonetic_weight(0.25);
+
+    let score = fuzzy("Schmidt", "Smith", &options);
+    assert!((score - (0.75 * unweighted + 0.25)).abs() < 1e-9);
+
+    // half of the term's words sound right
+    assert_lt!(
+        fuzzy("John Smith", "Jon Jones", &options),
+        fuzzy("John Smith", "Jon Smyth", &options)
+    );
+}
+
+#[test]
+fn should_find_phonetic_matches_below_the_edit_threshold() {
+    let candidates = vec!["Smith", "Smithers", "Schultz", "Kathryn"];
+    assert_eq!(
+        search("Schmidt", candidates.clone(), &FuzzyOptions::default()),
+        Ok(vec![])
+    );
+
+    let options = FuzzyOptions::default().phonetic(PhoneticAlgorithm::DoubleMetaphone);
+    assert_eq!(search("Schmidt", candidates, &options), Ok(vec!["Smith"]));
+}
+
+#[test]
+fn should_remove_phonetic_keys() {
+    let options = FuzzyOptions::default().phonetic(PhoneticAlgorithm::Soundex);
+    let mut searcher = Searcher::new(vec!["Robert", "Alice"], options);
+    assert_eq!(searcher.search("Rupert"), Ok(vec!["Robert"]));
+
+    searcher.remove_where(|name| *name == "Robert");
+    assert_eq!(searcher.search("Rupert"), Ok(vec![]));
+}
+
+#[test]
+#[should_panic]
+fn should_reject_weights_above_one() {
+    let _ = FuzzyOptions::<String>::default().phonetic_weight(1.5);
+}
@@ -0,0 +1,99 @@
+use super::{assert_same_as_brute_force, phrases};
+use crate::{
+    util::normalize, EditCosts, FuzzyOptions, PhoneticAlgorithm, QGramIndex, Searcher,
+    SubstitutionTable,
+};
+
+#[test]
+fn should_find_the_same_matches_as_brute_force() {
+    for threshold in [0.0, 0.4, 0.6, 0.8, 1.0] {
+        for q in 1..=4 {
+            let options = || FuzzyOptions::default().threshold(threshold);
+            assert_same_as_brute_force(options, QGramIndex::new().q(q));
+            assert_same_as_brute_force(|| options().use_sellers(false), QGramIndex::new().q(q));
+        }
+    }
+}
+
+#[test]
+fn should_allow_for_cheap_edits() {
+    let index = || QGramIndex::new().q(3);
+    assert_same_as_brute_force(
+        || {
+            FuzzyOptions::default().threshold(0.7).edit_costs(
+                EditCosts::default()
+                    .transpose(0.5)
+                    .substitutions(SubstitutionTable::new().with("b", "c", 0.25)),
+            )
+        },
+        index(),
+    );
+    assert_same_as_brute_force(
+        || {
+            FuzzyOptions::default()
+                .threshold(0.7)
+                .use_damerau(false)
+                .edit_costs(EditCosts::default().delete(0.5))
+        },
+        index(),
+    );
+    assert_same_as_brute_force(
+        || {
+            FuzzyOptions::default()
+                .threshold(0.8)
+                .phonetic(PhoneticAlgorithm::Soundex)
+        },
+        index(),
+    );
+    assert_same_as_brute_force(
+        || FuzzyOptions::default().threshold(0.8),
+        index().filter_strength(0.5),
+    );
+}
+
+fn indexed(mut index: QGramIndex, options: &FuzzyOptions<String>) -> QGramIndex {
+    for (i, phrase) in phrases().iter().enumerate() {
+        index.add(i, phrase, options);
+    }
+    index
+}
+
+#[test]
+fn should_narrow_down_the_keys_to_score() {
+    let options = FuzzyOptions::default().threshold(0.8);
+    let index = indexed(QGramIndex::new(), &options);
+
+    let term = normalize("gfedcbbcdefg".into(), &options).normal;
+    let filtered = index.filter(&term, &options).unwrap().len();
+    assert!(filtered < phrases().len() / 2, "{filtered}");
+
+    // a weaker filter lets more through
+    let mut weaker = indexed(QGramIndex::new().filter_strength(0.5), &options);
+    assert!(weaker.filter(&term, &options).unwrap().len() > filtered);
+
+    // a short term can't be narrowed down
+    let term = normalize("bcd".into(), &options).normal;
+    assert!(index.filter(&term, &options).is_none());
+
+    weaker.clear();
+    assert!(weaker.candidates().next().is_none());
+}
+
+#[test]
+fn should_add_and_remove_candidates() {
+    let mut searcher = Searcher::with_index(
+        vec!["hello world", "yellow world", "goodbye"],
+        FuzzyOptions::default(),
+        QGramIndex::new(),
+    );
+    assert_eq!(
+        searcher.search("hello"),
+        Ok(vec!["hello world", "yellow world"])
+    );
+
+    assert_eq!(searcher.remove_where(|phrase| phrase.starts_with('h')), 1);
+    assert_eq!(searcher.search("hello"), Ok(vec!["yellow world"]));
+
+    searcher.add(vec!["jello"]);
+    assert_eq!(searcher.search("hello"), Ok(vec!["jello", "yellow world"]));
+}
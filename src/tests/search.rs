@@ -31,7 +31,7 @@ fn should_have_good_relative_ordering() {
 }
 #[test]
 fn should_handle_empty_candidates() {
-    assert!(search("x", vec![""], &FuzzyOptions::default()).is_err());
+    assert!(search("x", vec![""], &FuzzyOptions::default()).is_ok());
 }
 
 #[test]
@@ -40,11 +40,11 @@ fn should_handle_unicode_well() {
     // unicode characters are normalized
     assert_eq!(t_search("\u{212B}", "\u{0041}\u{030A}"), 1.0);
     // handles high and low surrogates as single characters
-    assert_eq!(t_search("high", "h💩gh"), 0.5);
+    assert_eq!(t_search("high", "h💩gh"), 0.75);
     // handles combining marks as single characters
     assert_eq!(
         t_search("hi zalgo hello hello", "hi Z͑ͫ̓ͪ̂ͫ̽͏̴̙̤̞͉͚̯̞̠͍A̴̵̜̰͔ͫ͗͢L̠ͨͧͩ͘G̴̻͈͍͔̹̑͗̎̅͛́Ǫ̵̹̻̝̳͂̌̌͘ hello hello"),
-        0.5
+        0.75
     );
     // handles graphemes such as hangul jamo and joined emoji as single characters
     assert_eq!(t_search("abcde", "abc깍👨‍👩‍👧‍👦"), 0.6);
@@ -56,12 +56,12 @@ fn should_handle_unicode_well_with_use_separated_unicode() {
         .threshold(0.5);
     let t_search = |a, b| search_data(a, vec![b], &options).unwrap()[0].score;
     // unicode characters are normalized
-    assert_eq!(t_search("\0.2B", "\0.1\0.0A"), 0.1);
-    // handles high and low surrogates as multiple characters
-    assert_eq!(t_search("high", "h💩gh"), 0.5);
+    assert_eq!(t_search("\u{212B}", "\u{0041}\u{030A}"), 1.0);
+    // chars are unicode scalar values, so there are no surrogates to split
+    assert_eq!(t_search("high", "h💩gh"), 0.75);
     // handles combining marks as multiple characters
     assert_eq!(
-        t_search("hi zalgo hello hello".into(), "hi Z͑ͫ̓ͪ̂ͫ̽͏̴̙̤̞͉͚̯̞̠͍A̴̵̜̰͔ͫ͗͢L̠ͨͧͩ͘G̴̻͈͍͔̹̑͗̎̅͛́Ǫ̵̹̻̝̳͂̌̌͘ hello hello"),
+        t_search("hi zalgo hello hello", "hi Z͑ͫ̓ͪ̂ͫ̽͏̴̙̤̞͉͚̯̞̠͍A̴̵̜̰͔ͫ͗͢L̠ͨͧͩ͘G̴̻͈͍͔̹̑͗̎̅͛́Ǫ̵̹̻̝̳͂̌̌͘ hello hello"),
         0.6
     );
     // handles graphemes such as hangul jamo and joined emoji as multiple characters
@@ -174,12 +174,15 @@ mod options {
         assert_eq!(
             search_data("hello", vec!["hello"], &FuzzyOptions::default()).unwrap()[0],
             MatchData {
-                item: "hello".into(),
+                item: "hello",
                 original: "hello".into(),
                 key: "hello".into(),
-                score: 0.1,
+                score: 1.0,
                 match_index: 0,
                 match_length: 5,
+                spans: vec![(0, 5)],
+                expansion: None,
+                boost: None,
             }
         );
     }
@@ -193,7 +196,7 @@ mod options {
             "temperature",
             "myitem",
         ];
-        let options = FuzzyOptions::default().use_sellers(true);
+        let options = FuzzyOptions::default().use_sellers(false);
         assert_eq!(
             search("item", candidates, &options),
             Ok(vec!["item", "items", "myitem"]),
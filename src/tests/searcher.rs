@@ -1,6 +1,7 @@
 use more_asserts::assert_gt;
 
-use crate::{search, FuzzyOptions, Searcher};
+use super::random_strings;
+use crate::{search, FuzzyOptions, MatchKind, PhoneticAlgorithm, Searcher, Synonyms};
 
 #[test]
 fn should_return_the_same_results_as_search() {
@@ -57,3 +58,64 @@ fn should_allow_overriding_threshold() {
         .len(),
     );
 }
+
+// words over a few letters, so that many share a prefix
+fn words(count: usize, seed: u32) -> Vec<String> {
+    random_strings(seed, count, 0..8, "abcd")
+}
+
+#[test]
+fn should_search_many_terms_like_one_at_a_time() {
+    let terms = words(100, 0xdead_beef);
+    let options = [
+        || FuzzyOptions::default(),
+        || FuzzyOptions::default().threshold(0.4).use_sellers(false),
+        || FuzzyOptions::default().phonetic(PhoneticAlgorithm::Soundex),
+        || FuzzyOptions::default().match_kind(MatchKind::Path),
+    ];
+
+    for options in options {
+        let searcher = Searcher::new(words(200, 0x1234_5678), options());
+        let expected = terms
+            .iter()
+            .map(|term| searcher.search(term).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(searcher.search_many(&terms), Ok(expected));
+    }
+}
+
+#[test]
+fn should_search_many_terms_in_input_order() {
+    let searcher = Searcher::new(vec!["apple", "banana", "cherry"], FuzzyOptions::default())
+        .synonyms(Synonyms::new().add("fruit", ["banana"]));
+
+    assert_eq!(
+        searcher.search_many(&["cherry", "fruit", "apple", "cherry", "durian"]),
+        Ok(vec![
+            vec!["cherry"],
+            vec!["banana"],
+            vec!["apple"],
+            vec!["cherry"],
+            vec![],
+        ])
+    );
+    assert_eq!(searcher.search_many::<&str>(&[]), Ok(vec![]));
+}
+
+#[test]
+fn should_keep_searching_after_removing_most_candidates() {
+    let mut candidates = random_strings(7, 200, 4..8, "abcdef");
+    candidates.push("hello".to_string());
+    let mut searcher = Searcher::new(candidates, FuzzyOptions::default());
+
+    assert_eq!(searcher.remove_where(|candidate| candidate != "hello"), 200);
+    assert_eq!(searcher.len(), 1);
+    assert_eq!(searcher.search("helo"), Ok(vec!["hello".to_string()]));
+
+    searcher.add(vec!["help".to_string()]);
+    assert_eq!(
+        searcher.search("hel"),
+        Ok(vec!["help".to_string(), "hello".to_string()])
+    );
+    assert_eq!(searcher.suggest("helo", 1)[0].text, "hello");
+}
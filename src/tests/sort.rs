@@ -0,0 +1,105 @@
+use crate::{search, search_data, FuzzyOptions, MatchData, Searcher, SortKind, TieBreak};
+
+// every candidate contains `item`, so they all score 1
+fn candidates() -> Vec<&'static str> {
+    vec!["lineitem", "excitement", "itemize", "item"]
+}
+
+#[test]
+fn should_break_ties_by_the_default_chain() {
+    assert_eq!(
+        search("item", candidates(), &FuzzyOptions::default()),
+        search(
+            "item",
+            candidates(),
+            &FuzzyOptions::default().tie_breaks(TieBreak::DEFAULT)
+        ),
+    );
+    assert_eq!(
+        search("item", candidates(), &FuzzyOptions::default()),
+        Ok(vec!["item", "itemize", "excitement", "lineitem"]),
+    );
+}
+
+#[test]
+fn should_reorder_the_tie_break_chain() {
+    assert_eq!(
+        search(
+            "item",
+            candidates(),
+            &FuzzyOptions::default().tie_breaks([TieBreak::Score, TieBreak::KeyLength])
+        ),
+        Ok(vec!["item", "itemize", "lineitem", "excitement"]),
+    );
+    assert_eq!(
+        search(
+            "item",
+            candidates(),
+            &FuzzyOptions::default().tie_breaks([TieBreak::InsertOrder])
+        ),
+        Ok(vec!["lineitem", "excitement", "itemize", "item"]),
+    );
+
+    // without the score first, a worse match can come first
+    assert_eq!(
+        search(
+            "item",
+            vec!["itam", "item"],
+            &FuzzyOptions::default().tie_breaks([TieBreak::InsertOrder])
+        ),
+        Ok(vec!["itam", "item"]),
+    );
+}
+
+#[test]
+fn should_sort_by_key() {
+    assert_eq!(
+        search(
+            "item",
+            candidates(),
+            &FuzzyOptions::default().sort_by(SortKind::ByKey)
+        ),
+        Ok(vec!["excitement", "item", "itemize", "lineitem"]),
+    );
+
+    // keys are compared once normalized, so case is ignored
+    assert_eq!(
+        search(
+            "item",
+            vec!["Items", "item", "ITEM list"],
+            &FuzzyOptions::default().sort_by(SortKind::ByKey)
+        ),
+        Ok(vec!["item", "ITEM list", "Items"]),
+    );
+}
+
+#[test]
+fn should_sort_by_a_custom_comparator() {
+    let longest_first = || {
+        FuzzyOptions::default().sort_by(SortKind::Custom(Box::new(
+            |a: &MatchData<&str>, b: &MatchData<&str>| b.item.len().cmp(&a.item.len()),
+        )))
+    };
+    assert_eq!(
+        search("item", candidates(), &longest_first()),
+        Ok(vec!["excitement", "lineitem", "itemize", "item"]),
+    );
+
+    let searcher = Searcher::new(candidates(), longest_first());
+    assert_eq!(
+        searcher.search("item"),
+        Ok(vec!["excitement", "lineitem", "itemize", "item"]),
+    );
+}
+
+#[test]
+fn should_fall_back_to_the_chain_for_custom_ties() {
+    let by_score = FuzzyOptions::default().sort_by(SortKind::Custom(Box::new(|a, b| {
+        b.score.total_cmp(&a.score)
+    })));
+    let results = search_data("item", vec!["itam", "lineitem", "item"], &by_score).unwrap();
+    assert_eq!(
+        results.iter().map(|data| data.item).collect::<Vec<_>>(),
+        vec!["item", "lineitem", "itam"],
+    );
+}
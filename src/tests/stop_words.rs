@@ -0,0 +1,83 @@
+use more_asserts::assert_lt;
+
+use crate::{fuzzy, fuzzy_data, search, util::normalize, FuzzyOptions, Language, StopWords};
+
+#[test]
+fn should_ignore_stop_words_when_scoring() {
+    let options = FuzzyOptions::default().stop_words(StopWords::language(Language::English));
+    assert_eq!(fuzzy("the lord of the rings", "Lord Rings", &options), 1.0);
+    assert_eq!(fuzzy("lord rings", "The Lord of the Rings", &options), 1.0);
+    assert_lt!(
+        fuzzy(
+            "lord rings",
+            "The Lord of the Rings",
+            &FuzzyOptions::default()
+        ),
+        1.0
+    );
+}
+
+#[test]
+fn should_keep_stop_words_in_the_original() {
+    let options = FuzzyOptions::default().stop_words(StopWords::language(Language::English));
+    let normalized = normalize("The Lord of the Rings".to_string(), &options);
+    assert_eq!(normalized.key(), "lord rings");
+    assert_eq!(normalized.original, "The Lord of the Rings");
+
+    let data = fuzzy_data("lord rings", "The Lord of the Rings", &options);
+    assert_eq!(data.original, "The Lord of the Rings");
+    assert_eq!(data.match_index, 4);
+    assert_eq!(data.match_length, "Lord of the Rings".len());
+}
+
+#[test]
+fn should_keep_terms_of_only_stop_words() {
+    let options = FuzzyOptions::default().stop_words(StopWords::language(Language::English));
+    assert_eq!(fuzzy("the the", "The The", &options), 1.0);
+    assert_eq!(normalize("The The".to_string(), &options).key(), "the the");
+}
+
+#[test]
+fn should_use_lists_for_other_languages() {
+    let german = StopWords::language(Language::German);
+    assert!(german.contains("Der"));
+    assert!(!german.contains("the"));
+
+    let both = german.with_language(Language::French);
+    assert!(both.contains("les"));
+    assert!(both.contains("und"));
+}
+
+#[test]
+fn should_use_custom_stop_words() {
+    fn companies<T: ToString>() -> FuzzyOptions<T> {
+        FuzzyOptions::default().stop_words(StopWords::new(["Inc", "ltd"]))
+    }
+
+    assert_eq!(
+        normalize("Acme Inc".to_string(), &companies::<String>()).key(),
+        "acme"
+    );
+    assert_eq!(
+        search(
+            "acme",
+            vec!["Acme Inc", "Acme Widgets Ltd"],
+            &companies().threshold(1.0)
+        ),
+        Ok(vec!["Acme Inc", "Acme Widgets Ltd"])
+    );
+}
+
+#[test]
+fn should_drop_short_tokens() {
+    let options = FuzzyOptions::<String>::default().min_token_length(3);
+    assert_eq!(
+        normalize("a new hope".to_string(), &options).key(),
+        "new hope"
+    );
+    assert_eq!(
+        normalize("star wars ep iv".to_string(), &options).key(),
+        "star wars"
+    );
+    assert_eq!(normalize("ab".to_string(), &options).key(), "ab");
+}
@@ -0,0 +1,102 @@
+use crate::{FuzzyOptions, Searcher, Suggestion};
+
+fn books() -> Searcher<&'static str> {
+    Searcher::new(
+        vec![
+            "The Quick Brown Fox",
+            "Quick Recipes",
+            "Quirky Tales",
+            "Brown Bread",
+            "The Fox and the Hound",
+        ],
+        FuzzyOptions::default(),
+    )
+}
+
+fn texts(suggestions: Vec<Suggestion>) -> Vec<String> {
+    suggestions
+        .into_iter()
+        .map(|suggestion| suggestion.text)
+        .collect()
+}
+
+#[test]
+fn should_correct_whole_words() {
+    let suggestions = books().suggest("quikc", 3);
+    assert_eq!(
+        suggestions[0],
+        Suggestion {
+            text: "quick".into(),
+            distance: 1.0,
+            frequency: 2,
+        }
+    );
+
+    // a substring match would have taken "qui" for a perfect match
+    assert_eq!(books().suggest("qui", 1)[0].distance, 2.0);
+}
+
+#[test]
+fn should_rank_by_distance_then_frequency() {
+    let searcher = Searcher::new(
+        vec!["cart", "cart", "card", "care", "cat"],
+        FuzzyOptions::default(),
+    );
+    assert_eq!(
+        texts(searcher.suggest("carx", 5)),
+        vec!["cart", "card", "care", "cat"]
+    );
+    assert_eq!(searcher.suggest("carx", 5)[0].frequency, 2);
+}
+
+#[test]
+fn should_bound_the_distance() {
+    assert_eq!(books().suggest("xyzzy", 3), vec![]);
+    assert_eq!(texts(books().suggest("quck", 3)), vec!["quick"]);
+    assert_eq!(books().suggestion_distance(0.0).suggest("quck", 3), vec![]);
+}
+
+#[test]
+fn should_use_damerau_when_set() {
+    let damerau = books().suggest("qiuck", 1);
+    assert_eq!(damerau[0].distance, 1.0);
+
+    let levenshtein = Searcher::new(vec!["quick"], FuzzyOptions::default().use_damerau(false))
+        .suggest("qiuck", 1);
+    assert_eq!(levenshtein[0].distance, 2.0);
+}
+
+#[test]
+fn should_correct_queries_word_by_word() {
+    let suggestions = books().suggest("teh quikc brwn fxo", 2);
+    assert_eq!(suggestions[0].text, "the quick brown fox");
+    assert_eq!(suggestions[0].distance, 4.0);
+    assert_eq!(suggestions[0].frequency, 2);
+
+    // known words are kept, and unknown ones left as typed
+    assert_eq!(
+        texts(books().suggest("brown zzzzzzz", 1)),
+        vec!["brown zzzzzzz"]
+    );
+    assert_eq!(books().suggest("brown zzzzzzz", 1)[0].frequency, 0);
+}
+
+#[test]
+fn should_follow_changes_to_the_candidates() {
+    let mut searcher = books();
+    assert_eq!(texts(searcher.suggest("tales", 1)), vec!["tales"]);
+
+    searcher.remove_where(|book| book.starts_with("Quirky"));
+    searcher.add(vec!["Tablets"]);
+    assert_eq!(texts(searcher.suggest("tales", 1)), vec!["tablets"]);
+
+    // words are counted once for each key they still appear in
+    assert_eq!(searcher.suggest("brwn", 1)[0].frequency, 2);
+    searcher.remove_where(|book| book.starts_with("Brown"));
+    assert_eq!(searcher.suggest("brwn", 1)[0].frequency, 1);
+
+    searcher.clear();
+    assert_eq!(searcher.suggest("tales", 1), vec![]);
+    searcher.add(vec!["Tales"]);
+    assert_eq!(texts(searcher.suggest("tale", 1)), vec!["tales"]);
+}
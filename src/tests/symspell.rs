@@ -0,0 +1,83 @@
+use crate::{FuzzyOptions, Searcher, SymSpellIndex};
+
+fn words() -> Vec<&'static str> {
+    vec![
+        "apple",
+        "apply",
+        "ample",
+        "maple",
+        "applesauce",
+        "application",
+        "banana",
+        "bandana",
+        "cabana",
+    ]
+}
+
+#[test]
+fn should_find_the_same_words_as_the_trie() {
+    // the distance follows the threshold, so lower ones reach further
+    for threshold in [0.7, 0.5, 0.3] {
+        let options = || {
+            FuzzyOptions::default()
+                .use_sellers(false)
+                .threshold(threshold)
+        };
+        let trie = Searcher::new(words(), options());
+        let symspell = Searcher::with_index(words(), options(), SymSpellIndex::new());
+
+        for term in [
+            "apple", "appel", "aple", "banaan", "cabbana", "mapel", "xyz", "aplcatin", "apsauce",
+        ] {
+            assert_eq!(symspell.search_data(term), trie.search_data(term), "{term}");
+        }
+    }
+}
+
+#[test]
+fn should_bound_the_distance() {
+    let options = FuzzyOptions::default().use_sellers(false).threshold(0.0);
+    let symspell = Searcher::with_index(words(), options, SymSpellIndex::new().max_distance(1));
+    assert_eq!(symspell.search("appel"), Ok(vec!["apple", "apply"]));
+    assert_eq!(symspell.search("bnnaa"), Ok(vec![]));
+}
+
+#[test]
+fn should_find_long_keys_by_their_prefix() {
+    let symspell = Searcher::with_index(
+        words(),
+        FuzzyOptions::default().use_sellers(false).threshold(0.7),
+        SymSpellIndex::new().prefix_length(4),
+    );
+    assert_eq!(symspell.search("aplication"), Ok(vec!["application"]));
+    assert_eq!(symspell.search("applesause"), Ok(vec!["applesauce"]));
+}
+
+#[test]
+fn should_add_and_remove_candidates() {
+    let mut symspell = Searcher::with_index(
+        words(),
+        FuzzyOptions::default().use_sellers(false).threshold(0.7),
+        SymSpellIndex::new(),
+    );
+    assert_eq!(symspell.remove_where(|word| word.starts_with("ban")), 2);
+    assert_eq!(symspell.search("banana"), Ok(vec![]));
+
+    symspell.add(vec!["bananas"]);
+    assert_eq!(symspell.search("banana"), Ok(vec!["bananas"]));
+
+    symspell.clear();
+    assert_eq!(symspell.search("banana"), Ok(vec![]));
+    symspell.add(vec!["banana"]);
+    assert_eq!(symspell.search("banana"), Ok(vec!["banana"]));
+}
+
+#[test]
+fn should_suggest_from_its_keys() {
+    let symspell = Searcher::with_index(
+        words(),
+        FuzzyOptions::default().use_sellers(false).threshold(0.7),
+        SymSpellIndex::new(),
+    );
+    assert_eq!(symspell.suggest("bandaan", 1)[0].text, "bandana");
+}
@@ -0,0 +1,137 @@
+use std::fs;
+
+use crate::{Expansion, FuzzyOptions, Searcher, Synonyms, SynonymsError};
+
+fn catalog() -> Searcher<&'static str> {
+    Searcher::new(
+        vec![
+            "Motor mount",
+            "Meter cable",
+            "Television stand",
+            "Flat screen monitor",
+        ],
+        FuzzyOptions::default(),
+    )
+}
+
+fn parts() -> Synonyms {
+    Synonyms::new()
+        .add("mtr", ["motor"])
+        .add("tv", ["television"])
+        .add("flat screen tv", ["flat screen monitor"])
+}
+
+#[test]
+fn should_match_expanded_terms() {
+    assert_eq!(catalog().search("mtr"), Ok(vec![]));
+    assert_eq!(
+        catalog().synonyms(parts()).search("mtr"),
+        Ok(vec!["Motor mount"])
+    );
+    assert_eq!(
+        catalog().synonyms(parts()).search("tv stand"),
+        Ok(vec!["Television stand"])
+    );
+}
+
+#[test]
+fn should_prefer_multi_word_synonyms() {
+    let results = catalog()
+        .synonyms(parts())
+        .search_data("flat screen tv")
+        .unwrap();
+    assert_eq!(results[0].item, "Flat screen monitor");
+    assert_eq!(
+        results[0].expansion.as_ref().unwrap().replacements,
+        vec![("flat screen tv".into(), "flat screen monitor".into())]
+    );
+}
+
+#[test]
+fn should_penalize_expanded_matches() {
+    let results = catalog()
+        .synonyms(parts().penalty(0.1))
+        .search_data("mtr")
+        .unwrap();
+    assert_eq!(results[0].score, 0.9);
+    assert_eq!(
+        results[0].expansion,
+        Some(Expansion {
+            query: "motor".into(),
+            replacements: vec![("mtr".into(), "motor".into())],
+            penalty: 0.1,
+        })
+    );
+
+    // a match of the query as typed is not explained by an expansion
+    let results = catalog()
+        .synonyms(parts())
+        .search_data("television")
+        .unwrap();
+    assert_eq!(results[0].score, 1.0);
+    assert_eq!(results[0].expansion, None);
+}
+
+#[test]
+fn should_keep_the_case_of_words_as_typed() {
+    let synonyms = Synonyms::new().add("tv", ["television"]);
+    let expansions = synonyms.expand("TV Stand");
+    assert_eq!(expansions.len(), 1);
+    assert_eq!(expansions[0].query, "television Stand");
+    assert_eq!(
+        expansions[0].replacements,
+        vec![("tv".into(), "television".into())]
+    );
+}
+
+#[test]
+fn should_parse_text_and_tsv() {
+    let synonyms = Synonyms::parse(
+        "# abbreviations\n\
+         st = street, saint\n\
+         \n\
+         tv\ttelevision\n\
+         flat screen tv\tflat screen monitor\n",
+    )
+    .unwrap();
+
+    let queries = synonyms
+        .expand("st tv")
+        .into_iter()
+        .map(|expansion| expansion.query)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        queries,
+        vec![
+            "st television",
+            "street tv",
+            "street television",
+            "saint tv",
+            "saint television"
+        ]
+    );
+
+    assert!(matches!(
+        Synonyms::parse("st = street\nmtr\n"),
+        Err(SynonymsError::MissingAlternatives(2))
+    ));
+    assert!(matches!(
+        Synonyms::parse("tv\t\n"),
+        Err(SynonymsError::MissingAlternatives(1))
+    ));
+}
+
+#[test]
+fn should_load_from_a_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("synonyms.tsv");
+    fs::write(&path, "mtr\tmotor\n").unwrap();
+
+    let searcher = catalog().synonyms(Synonyms::load(&path).unwrap());
+    assert_eq!(searcher.search("mtr"), Ok(vec!["Motor mount"]));
+
+    assert!(matches!(
+        Synonyms::load(dir.path().join("missing.tsv")),
+        Err(SynonymsError::Io(_))
+    ));
+}
@@ -0,0 +1,91 @@
+use more_asserts::assert_lt;
+
+use crate::{fuzzy, fuzzy_data, search, search_data, FuzzyOptions, Searcher};
+
+#[test]
+fn should_match_pinyin() {
+    let options = FuzzyOptions::default().transliterate(true);
+    assert_eq!(fuzzy("beijing", "北京", &options), 1.0);
+    assert_eq!(fuzzy("běijīng", "北京", &options), 1.0);
+    assert_eq!(fuzzy("bj", "北京", &options), 1.0);
+    assert_eq!(fuzzy("shanghai", "上海市", &options), 1.0);
+
+    assert_lt!(fuzzy("beijing", "北京", &FuzzyOptions::default()), 0.5);
+}
+
+#[test]
+fn should_match_romaji() {
+    let options = FuzzyOptions::default().transliterate(true);
+    assert_eq!(fuzzy("tokyo", "とうきょう", &options), 1.0);
+    assert_eq!(fuzzy("toukyou", "トウキョウ", &options), 1.0);
+    assert_eq!(fuzzy("kohi", "コーヒー", &options), 1.0);
+    assert_eq!(fuzzy("matcha", "まっちゃ", &options), 1.0);
+    assert_eq!(fuzzy("kippu", "きっぷ", &options), 1.0);
+    assert_eq!(fuzzy("shashin", "しゃしん", &options), 1.0);
+    assert_eq!(fuzzy("fan", "ファン", &options), 1.0);
+}
+
+#[test]
+fn should_read_kanji_words_in_japanese() {
+    let options = FuzzyOptions::default().transliterate(true);
+    assert_eq!(fuzzy("tokyo", "東京", &options), 1.0);
+    assert_eq!(fuzzy("dongjing", "東京", &options), 1.0);
+    assert_eq!(fuzzy("osaka", "大阪", &options), 1.0);
+    assert_eq!(fuzzy("hokkaido", "北海道", &options), 1.0);
+    assert_eq!(fuzzy("tokyoeki", "東京駅", &options), 1.0);
+    assert_eq!(fuzzy("matcha", "抹茶", &options), 1.0);
+}
+
+#[test]
+fn should_report_against_the_original_key() {
+    let options = FuzzyOptions::default().transliterate(true);
+    let data = fuzzy_data("beijing", "北京", &options);
+    assert_eq!(data.original, "北京");
+    assert_eq!(data.match_index, 0);
+    assert_eq!(data.match_length, "北京".len());
+
+    // half of the pinyin highlights the character it came from
+    let data = fuzzy_data("jing", "北京", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, "北".len());
+    assert_eq!(data.match_length, "京".len());
+
+    // a shortened long vowel is highlighted with its syllable, which is the
+    // first one to match here
+    let data = fuzzy_data("kyo", "東京 とうきょう", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, "東".len());
+    assert_eq!(data.match_length, "京".len());
+
+    let data = fuzzy_data("kyo", "とうきょう", &options);
+    assert_eq!(data.score, 1.0);
+    assert_eq!(data.match_index, "とう".len());
+    assert_eq!(data.match_length, "きょう".len());
+}
+
+#[test]
+fn should_search_transliterated_keys() {
+    let options = FuzzyOptions::default().transliterate(true);
+    let candidates = vec!["上海", "北京", "Beijing Road"];
+    assert_eq!(
+        search("beijing", candidates.clone(), &options),
+        Ok(vec!["北京", "Beijing Road"])
+    );
+
+    let results = search_data("bj", candidates, &options).unwrap();
+    assert_eq!(results[0].item, "北京");
+    assert_eq!(results[0].spans, vec![(0, "北京".len())]);
+}
+
+#[test]
+fn should_remove_transliterated_keys() {
+    let mut searcher = Searcher::new(
+        vec!["北京", "南京"],
+        FuzzyOptions::default().transliterate(true),
+    );
+    assert_eq!(searcher.search("nanjing"), Ok(vec!["南京"]));
+
+    searcher.remove_where(|city| *city == "南京");
+    assert_eq!(searcher.search("nanjing"), Ok(vec![]));
+    assert_eq!(searcher.search("beijing"), Ok(vec!["北京"]));
+}
@@ -0,0 +1,113 @@
+use std::{
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{FileSearchOptions, FileSearcher, FileWatcher};
+
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+fn watcher(root: &tempfile::TempDir) -> FileWatcher {
+    let searcher = FileSearcher::new(root.path(), FileSearchOptions::default()).unwrap();
+    FileWatcher::new(searcher, DEBOUNCE).unwrap()
+}
+
+// waits for the index to settle on `expected` results for `term`
+fn wait_for(watcher: &FileWatcher, term: &str, expected: &[&str]) {
+    let expected = expected.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let started = Instant::now();
+
+    while watcher.search(term).unwrap() != expected {
+        assert!(
+            started.elapsed() < Duration::from_secs(10),
+            "expected {expected:?} for {term}, got {:?}",
+            watcher.search(term).unwrap()
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn should_follow_created_and_deleted_files() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("main.rs"), "").unwrap();
+    let watcher = watcher(&root);
+    let generation = watcher.generation();
+
+    fs::write(root.path().join("lib.rs"), "").unwrap();
+    wait_for(&watcher, "lib", &["lib.rs"]);
+    assert!(watcher.generation() > generation);
+
+    fs::remove_file(root.path().join("lib.rs")).unwrap();
+    wait_for(&watcher, "lib", &[]);
+    assert_eq!(watcher.searcher().len(), 1);
+}
+
+#[test]
+fn should_follow_renames() {
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir(root.path().join("src")).unwrap();
+    fs::write(root.path().join("src/old.rs"), "").unwrap();
+    let watcher = watcher(&root);
+
+    fs::rename(
+        root.path().join("src/old.rs"),
+        root.path().join("src/new.rs"),
+    )
+    .unwrap();
+    wait_for(&watcher, "new", &["src/new.rs"]);
+    wait_for(&watcher, "old", &[]);
+
+    // moving a directory moves everything in it
+    fs::rename(root.path().join("src"), root.path().join("lib")).unwrap();
+    wait_for(&watcher, "new", &["lib/new.rs"]);
+}
+
+#[test]
+fn should_index_new_directories() {
+    let root = tempfile::tempdir().unwrap();
+    let watcher = watcher(&root);
+
+    fs::create_dir_all(root.path().join("a/b")).unwrap();
+    fs::write(root.path().join("a/b/deep.txt"), "").unwrap();
+    wait_for(&watcher, "deep", &["a/b/deep.txt"]);
+
+    fs::remove_dir_all(root.path().join("a")).unwrap();
+    wait_for(&watcher, "deep", &[]);
+}
+
+#[test]
+fn should_debounce_bursts() {
+    let root = tempfile::tempdir().unwrap();
+    let watcher = watcher(&root);
+    let generation = watcher.generation();
+
+    for i in 0..50 {
+        fs::write(root.path().join(format!("file{i}.txt")), "").unwrap();
+    }
+
+    let started = Instant::now();
+    while watcher.searcher().len() < 50 {
+        assert!(started.elapsed() < Duration::from_secs(10));
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert!(watcher.generation() - generation < 50);
+}
+
+#[test]
+fn should_honor_ignore_files() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join(".gitignore"), "target/\n").unwrap();
+    let watcher = watcher(&root);
+
+    fs::create_dir(root.path().join("target")).unwrap();
+    fs::write(root.path().join("target/build.rs"), "").unwrap();
+    fs::write(root.path().join("build.rs"), "").unwrap();
+    wait_for(&watcher, "build", &["build.rs"]);
+
+    fs::write(root.path().join(".gitignore"), "").unwrap();
+    wait_for(&watcher, "build", &["build.rs", "target/build.rs"]);
+}
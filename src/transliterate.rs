@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use pinyin::ToPinyin;
+
+use crate::fold::fold_kana;
+
+lazy_static! {
+    // the kana of each character of a word, or of the whole word when they
+    // can't be told apart
+    static ref KANJI_READINGS: HashMap<&'static str, Vec<&'static str>> =
+        include_str!("data/kanji_readings.txt")
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let (word, reading) = line.split_once('\t')?;
+                Some((word, reading.split('|').collect()))
+            })
+            .collect();
+    static ref LONGEST_KANJI_WORD: usize = KANJI_READINGS
+        .keys()
+        .map(|word| word.chars().count())
+        .max()
+        .unwrap_or_default();
+}
+
+/// A romanized form of a key, with the byte of the key that each of its bytes
+/// came from.
+pub(crate) struct Romanized {
+    pub(crate) text: String,
+    // one entry per byte of `text`, and one more for its end
+    pub(crate) offsets: Vec<usize>,
+}
+
+impl Romanized {
+    fn new(key: &str, chunks: Vec<(usize, String)>) -> Self {
+        let mut text = String::new();
+        let mut offsets = vec![];
+
+        for (index, chunk) in chunks {
+            text.push_str(&chunk);
+            offsets.extend(std::iter::repeat_n(index, chunk.len()));
+        }
+        offsets.push(key.len());
+
+        Self { text, offsets }
+    }
+}
+
+/// Romanizes the Chinese characters and kana in `key`: pinyin without tones,
+/// with tone marks and as initials only, and Hepburn romaji both as spelled
+/// and with long vowels shortened. Kanji have their Chinese reading, and the
+/// place names and common words of a built-in dictionary also have their
+/// Japanese one, so that 東京 is found by `tokyo` as well as `dongjing`.
+/// Other characters are kept as they are.
+///
+/// Returns no forms for keys without either script.
+pub(crate) fn romanize(key: &str) -> Vec<Romanized> {
+    let mut forms = vec![];
+
+    if key.chars().any(|char| char.to_pinyin().is_some()) {
+        forms.push(pinyin(key, |pinyin| pinyin.plain()));
+        forms.push(pinyin(key, |pinyin| pinyin.with_tone()));
+        forms.push(pinyin(key, |pinyin| pinyin.first_letter()));
+    }
+
+    let read = read_kanji(key);
+    if read
+        .iter()
+        .any(|&(_, char)| syllable(fold_kana(char)).is_some())
+    {
+        let chunks = romaji(&read);
+        forms.push(Romanized::new(key, shorten_long_vowels(&chunks)));
+        forms.push(Romanized::new(key, chunks));
+    }
+
+    forms
+}
+
+fn pinyin(key: &str, form: impl Fn(pinyin::Pinyin) -> &'static str) -> Romanized {
+    let chunks = key
+        .char_indices()
+        .map(|(index, char)| {
+            let chunk = char
+                .to_pinyin()
+                .map_or_else(|| char.to_string(), |pinyin| form(pinyin).to_string());
+            (index, chunk)
+        })
+        .collect();
+
+    Romanized::new(key, chunks)
+}
+
+// The characters of `key` with the words of the dictionary replaced by their
+// kana, each with the byte of the key it came from. The longest word starting
+// at a character wins.
+fn read_kanji(key: &str) -> Vec<(usize, char)> {
+    let chars = key.char_indices().collect::<Vec<_>>();
+    let mut read = vec![];
+    let mut start = 0;
+
+    while start < chars.len() {
+        let word = (1..=(chars.len() - start).min(*LONGEST_KANJI_WORD))
+            .rev()
+            .find_map(|length| {
+                let end = chars
+                    .get(start + length)
+                    .map_or(key.len(), |&(index, _)| index);
+                Some(length).zip(KANJI_READINGS.get(&key[chars[start].0..end]))
+            });
+
+        let Some((length, readings)) = word else {
+            read.push(chars[start]);
+            start += 1;
+            continue;
+        };
+
+        for (offset, reading) in readings.iter().enumerate() {
+            let index = chars[start + offset].0;
+            read.extend(reading.chars().map(|char| (index, char)));
+        }
+        start += length;
+    }
+
+    read
+}
+
+fn romaji(read: &[(usize, char)]) -> Vec<(usize, String)> {
+    let mut chunks: Vec<(usize, String)> = vec![];
+    let mut geminate = None;
+
+    for &(index, char) in read {
+        let kana = fold_kana(char);
+        let last = chunks.last_mut().filter(|_| geminate.is_none());
+
+        match (kana, last) {
+            // small ya, yu and yo make a digraph with the syllable before
+            ('ゃ' | 'ゅ' | 'ょ', Some((_, chunk))) if chunk.ends_with('i') && chunk.len() > 1 => {
+                chunk.pop();
+                if !(chunk.ends_with("sh") || chunk.ends_with("ch") || chunk.ends_with('j')) {
+                    chunk.push('y');
+                }
+                chunk.push_str(&syllable(kana).unwrap_or_default()[1..]);
+            }
+            // as do small vowels, in loanwords such as ファン or ティー
+            ('ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ', Some((_, chunk))) if is_romaji(chunk) => {
+                chunk.pop();
+                if chunk.is_empty() {
+                    chunk.push('w');
+                }
+                chunk.push_str(&syllable(kana).unwrap_or_default());
+            }
+            ('ー', Some((_, chunk))) if is_romaji(chunk) => {
+                let vowel = chunk.chars().next_back().unwrap_or_default();
+                chunks.push((index, vowel.to_string()));
+            }
+            ('っ', _) => geminate = Some(index),
+            _ => {
+                let Some(chunk) = syllable(kana) else {
+                    geminate = None;
+                    chunks.push((index, char.to_string()));
+                    continue;
+                };
+
+                // a small tsu doubles the consonant after it
+                if let Some(geminate) = geminate.take() {
+                    let consonant = if chunk.starts_with("ch") {
+                        't'
+                    } else {
+                        chunk.as_bytes()[0] as char
+                    };
+                    if !is_vowel(consonant) {
+                        chunks.push((geminate, consonant.to_string()));
+                    }
+                }
+
+                chunks.push((index, chunk));
+            }
+        }
+    }
+
+    chunks
+}
+
+// Drops the second vowel of `ou`, `oo` and `uu` across syllables, so that
+// とうきょう reads as `tokyo`.
+fn shorten_long_vowels(chunks: &[(usize, String)]) -> Vec<(usize, String)> {
+    let mut shortened: Vec<(usize, String)> = vec![];
+
+    for (index, chunk) in chunks {
+        let previous = shortened
+            .last()
+            .and_then(|(_, chunk)| chunk.chars().next_back());
+        let long = matches!(
+            (previous, chunk.as_str()),
+            (Some('o'), "u" | "o") | (Some('u'), "u")
+        );
+
+        if !long {
+            shortened.push((*index, chunk.clone()));
+        }
+    }
+
+    shortened
+}
+
+fn is_vowel(char: char) -> bool {
+    matches!(char, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+fn is_romaji(chunk: &str) -> bool {
+    chunk.ends_with(is_vowel) && chunk.is_ascii()
+}
+
+// The Hepburn romanization of a single hiragana.
+fn syllable(kana: char) -> Option<String> {
+    let romaji = match kana {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' | 'ゐ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' | 'ゑ' => "e",
+        'お' | 'ぉ' | 'を' => "o",
+        'か' | 'ゕ' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' | 'ゖ' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    };
+
+    Some(romaji.to_string())
+}
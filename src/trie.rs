@@ -1,40 +1,494 @@
+use std::{cmp::Ordering, collections::BTreeMap, collections::HashMap};
+
 use crate::{
-    util::{normalize, Normalized},
-    FuzzyOptions,
+    fuzzy_match, phonetic,
+    score::{ScoreResult, Scorer},
+    sort_matches,
+    util::{normalize, normalize_key, Normalized},
+    Expansion, FuzzyOptions, TieBreak,
 };
 
-#[allow(dead_code)]
-pub struct Candidate<T> {
-    index: usize,
-    key_index: usize,
-    item: T,
-    normalized: Normalized,
+#[derive(Clone)]
+pub struct Candidate {
+    pub(crate) index: usize,
+    pub(crate) key_index: usize,
+    pub(crate) normalized: Normalized,
+}
+
+#[derive(Default)]
+pub struct Node {
+    children: BTreeMap<String, Node>,
+    candidates: Vec<Candidate>,
+    // the number of graphemes in the longest key below this node
+    depth: usize,
+}
+
+/// A scored key, before it has been resolved back to its item.
+#[derive(Clone, Debug)]
+pub struct TrieMatch {
+    pub(crate) index: usize,
+    pub(crate) key_index: usize,
+    pub(crate) score: f64,
+    pub(crate) key: String,
+    pub(crate) original: String,
+    pub(crate) match_index: usize,
+    pub(crate) match_length: usize,
+    pub(crate) spans: Vec<(usize, usize)>,
+    pub(crate) length_diff: usize,
+    pub(crate) expansion: Option<Expansion>,
+    pub(crate) boost: Option<f64>,
+}
+
+impl TrieMatch {
+    /// Builds a match from a `start..start + length` range of the candidate's
+    /// normalized graphemes.
+    pub(crate) fn new(
+        candidate: &Candidate,
+        score: f64,
+        start: usize,
+        length: usize,
+        term_length: usize,
+    ) -> Self {
+        let normalized = &candidate.normalized;
+        let (match_index, match_length) = normalized.denormalize(start, length);
+
+        Self {
+            index: candidate.index,
+            key_index: candidate.key_index,
+            score,
+            key: normalized.key(),
+            original: normalized.original.clone(),
+            match_index,
+            match_length,
+            spans: if match_length > 0 {
+                vec![(match_index, match_length)]
+            } else {
+                vec![]
+            },
+            length_diff: normalized.normal.len().abs_diff(term_length),
+            expansion: None,
+            boost: None,
+        }
+    }
+
+    /// Scores every key of `item`, numbered `index`, against `term` as a
+    /// search would, returning the best if it reaches the threshold. This is
+    /// how an [`Index`](crate::Index) outside the crate scores what it finds.
+    pub fn score_item<T, U>(
+        term: &str,
+        index: usize,
+        item: &T,
+        options: &FuzzyOptions<T, U>,
+    ) -> Option<Self> {
+        let normal_term = normalize(term.to_string(), options).normal;
+
+        options
+            .keys(item)
+            .into_iter()
+            .enumerate()
+            .map(|(key_index, key)| fuzzy_match(term, &normal_term, index, key_index, key, options))
+            .filter(|result| result.score >= options.threshold)
+            .min_by(compare_matches)
+    }
+
+    /// Sorts matches in the order [`Index::query`](crate::Index::query)
+    /// returns them.
+    pub fn sort<T, U>(matches: &mut [Self], options: &FuzzyOptions<T, U>) {
+        sort_matches(matches, options);
+    }
+
+    /// The number of the matched item.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Which of the item's keys matched.
+    pub fn key_index(&self) -> usize {
+        self.key_index
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
 }
 
-#[allow(dead_code)]
-pub struct Trie {}
+/// A trie of the normalized keys, walked with the edit distance matrix so
+/// that keys sharing a prefix share its rows. It suits every option, and is
+/// the index a [`Searcher`](crate::Searcher) uses by default.
+#[derive(Default)]
+pub struct Trie {
+    root: Node,
+    // candidates by the phonetic code of each of their words, when phonetic
+    // matching is on
+    phonetic: HashMap<String, Vec<Candidate>>,
+}
+
+struct SearchState<'s> {
+    term: &'s [String],
+    scorer: Scorer<'s>,
+    threshold: f64,
+    path: Vec<&'s str>,
+    rows: Vec<Vec<f64>>,
+    results: HashMap<usize, TrieMatch>,
+}
 
 impl Trie {
-    #[allow(unused_variables, dead_code, unused_mut)]
-    pub fn new<'a, T>(mut index: usize, items: &'a Vec<T>, options: FuzzyOptions<'a, T>) -> Self {
-        let mut this = Self {};
-        let key_selector = &options.key_selector;
+    pub(crate) fn new<T, U>(mut index: usize, items: &[T], options: &FuzzyOptions<T, U>) -> Self {
+        let mut this = Self::default();
 
         for item in items.iter() {
-            let candidates = key_selector(item)
-                .into_iter()
-                .enumerate()
-                .map(|(index, key)| Candidate {
+            this.add(index, item, options);
+            index += 1;
+        }
+
+        this
+    }
+
+    pub(crate) fn add<T, U>(&mut self, index: usize, item: &T, options: &FuzzyOptions<T, U>) {
+        let keys = (options.key_selector)(item);
+
+        for (key_index, key) in keys.into_iter().enumerate() {
+            if let Some(algorithm) = options.phonetic {
+                let candidate = Candidate {
+                    index,
+                    key_index,
+                    normalized: normalize(key.clone(), options),
+                };
+                for code in phonetic::encode(&key, algorithm).into_iter().flatten() {
+                    let candidates = self.phonetic.entry(code).or_default();
+                    if !candidates
+                        .iter()
+                        .any(|existing| existing.index == index && existing.key_index == key_index)
+                    {
+                        candidates.push(candidate.clone());
+                    }
+                }
+            }
+
+            for normalized in normalize_key(key, options) {
+                self.insert(Candidate {
                     index,
-                    key_index: index,
-                    item: item.clone(),
-                    normalized: normalize(key, &options),
+                    key_index,
+                    normalized,
                 });
-            index += 1;
+            }
+        }
+    }
 
-            for candidate in candidates {}
+    fn insert(&mut self, candidate: Candidate) {
+        let length = candidate.normalized.normal.len();
+        let mut node = &mut self.root;
+        node.depth = node.depth.max(length);
+
+        for (i, grapheme) in candidate.normalized.normal.iter().enumerate() {
+            node = node.children.entry(grapheme.clone()).or_default();
+            node.depth = node.depth.max(length - i - 1);
         }
 
-        Self {}
+        node.candidates.push(candidate);
+    }
+
+    pub(crate) fn remove<T, U>(&mut self, index: usize, item: &T, options: &FuzzyOptions<T, U>) {
+        for key in (options.key_selector)(item) {
+            if let Some(algorithm) = options.phonetic {
+                for code in phonetic::encode(&key, algorithm).into_iter().flatten() {
+                    if let Some(candidates) = self.phonetic.get_mut(&code) {
+                        candidates.retain(|candidate| candidate.index != index);
+                        if candidates.is_empty() {
+                            self.phonetic.remove(&code);
+                        }
+                    }
+                }
+            }
+
+            for normalized in normalize_key(key, options) {
+                remove_from(&mut self.root, &normalized.normal, index);
+            }
+        }
+    }
+
+    pub(crate) fn candidates(&self) -> impl Iterator<Item = &Candidate> {
+        let mut stack = vec![&self.root];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.values());
+            Some(node.candidates.iter())
+        })
+        .flatten()
+    }
+
+    /// The candidates with a word that has one of `codes`.
+    pub(crate) fn phonetic_candidates<'t>(
+        &'t self,
+        codes: &'t [Vec<String>],
+    ) -> impl Iterator<Item = &'t Candidate> {
+        codes
+            .iter()
+            .flatten()
+            .filter_map(|code| self.phonetic.get(code))
+            .flatten()
+    }
+
+    pub(crate) fn search<T, U>(
+        &self,
+        term: &[String],
+        options: &FuzzyOptions<T, U>,
+    ) -> HashMap<usize, TrieMatch> {
+        let mut state = SearchState::new(term, self.root.depth, options);
+        state.visit(&self.root, f64::INFINITY);
+
+        state.results
+    }
+
+    /// Searches for several terms in a single walk, visiting each node once
+    /// for all the terms that haven't pruned it. Terms sharing a prefix prune
+    /// much the same nodes, so they are best searched together. The rows of
+    /// the edit distances for the prefix they all share are filled in once
+    /// per node, and each term only fills in the rows of the rest of it.
+    pub(crate) fn search_group<T, U>(
+        &self,
+        terms: &[Vec<String>],
+        options: &FuzzyOptions<T, U>,
+    ) -> Vec<HashMap<usize, TrieMatch>> {
+        let Some(first) = terms.first() else {
+            return vec![];
+        };
+        let shared = terms
+            .iter()
+            .map(|term| first.iter().zip(term).take_while(|(a, b)| a == b).count())
+            .min()
+            .unwrap_or_default();
+
+        let depth = self.root.depth;
+        let mut prefix = SearchState::new(&first[..shared], depth, options);
+        let mut states = terms
+            .iter()
+            .map(|term| SearchState::new(term, depth, options))
+            .collect::<Vec<_>>();
+
+        let active = (0..states.len())
+            .map(|i| (i, f64::INFINITY))
+            .collect::<Vec<_>>();
+        // the states still walking at each depth, kept between nodes
+        let mut buffers = vec![vec![]; depth + 1];
+        visit_group(&self.root, &mut states, &mut prefix, &active, &mut buffers);
+
+        states.into_iter().map(|state| state.results).collect()
+    }
+}
+
+// Visits `node` for each `(state, best)` in `active`, descending into the
+// children for the states that don't prune them. `prefix` follows the walk
+// to fill in the rows of the prefix the terms share.
+fn visit_group<'s>(
+    node: &'s Node,
+    states: &mut [SearchState<'s>],
+    prefix: &mut SearchState<'s>,
+    active: &[(usize, f64)],
+    buffers: &mut [Vec<(usize, f64)>],
+) {
+    let Some((next, buffers)) = buffers.split_first_mut() else {
+        return;
+    };
+    next.clear();
+    next.extend(
+        active
+            .iter()
+            .filter_map(|&(i, best)| Some((i, states[i].enter(node, best)?))),
+    );
+
+    // a term left on its own walks the rest without the bookkeeping
+    if let [(i, best)] = next[..] {
+        states[i].visit_children(node, best);
+        return;
     }
+
+    for (grapheme, child) in &node.children {
+        prefix.descend(grapheme);
+        for &(i, _) in next.iter() {
+            states[i].descend_shared(grapheme, &prefix.rows);
+        }
+        visit_group(child, states, prefix, next, buffers);
+        prefix.path.pop();
+        for &(i, _) in next.iter() {
+            states[i].path.pop();
+        }
+    }
+}
+
+fn remove_from(node: &mut Node, path: &[String], index: usize) {
+    match path.split_first() {
+        None => node.candidates.retain(|candidate| candidate.index != index),
+        Some((grapheme, rest)) => {
+            if let Some(child) = node.children.get_mut(grapheme) {
+                remove_from(child, rest, index);
+                if child.candidates.is_empty() && child.children.is_empty() {
+                    node.children.remove(grapheme);
+                }
+            }
+        }
+    }
+
+    node.depth = node
+        .children
+        .values()
+        .map(|child| child.depth + 1)
+        .max()
+        .unwrap_or(0);
+}
+
+impl<'s> SearchState<'s> {
+    // a search for `term` in a trie of keys up to `depth` graphemes long
+    fn new<T, U>(term: &'s [String], depth: usize, options: &'s FuzzyOptions<T, U>) -> Self {
+        let scorer = Scorer::new(options);
+        let rows = scorer.init_rows(term.len() + 1, depth + 1);
+
+        Self {
+            term,
+            scorer,
+            threshold: options.threshold,
+            path: vec![],
+            rows,
+            results: HashMap::new(),
+        }
+    }
+
+    // `best` is the lowest distance on the last row so far, which is where a
+    // Sellers match can end early
+    fn visit(&mut self, node: &'s Node, best: f64) {
+        if let Some(best) = self.enter(node, best) {
+            self.visit_children(node, best);
+        }
+    }
+
+    fn visit_children(&mut self, node: &'s Node, best: f64) {
+        for (grapheme, child) in &node.children {
+            self.descend(grapheme);
+            self.visit(child, best);
+            self.path.pop();
+        }
+    }
+
+    // Scores the keys ending at `node`, returning the new `best` if its
+    // children are worth visiting.
+    fn enter(&mut self, node: &'s Node, best: f64) -> Option<f64> {
+        let column = self.path.len();
+        let last = self.rows[self.term.len()][column];
+        let best = best.min(last);
+
+        if !node.candidates.is_empty() {
+            self.add_candidates(node);
+        }
+
+        if node.children.is_empty() || self.prune(node, column, best) {
+            None
+        } else {
+            Some(best)
+        }
+    }
+
+    // fills in the column for the child reached by `grapheme`
+    fn descend(&mut self, grapheme: &'s str) {
+        let column = self.path.len();
+        self.path.push(grapheme);
+        self.scorer
+            .score_column(self.term, &self.path, &mut self.rows, column);
+    }
+
+    // as `descend`, but taking the rows of a prefix of the term from `shared`
+    // rather than filling them in again
+    fn descend_shared(&mut self, grapheme: &'s str, shared: &[Vec<f64>]) {
+        let column = self.path.len();
+        self.path.push(grapheme);
+        for (row, shared) in self.rows.iter_mut().zip(shared).skip(1) {
+            row[column + 1] = shared[column + 1];
+        }
+        self.scorer.score_column_from(
+            self.term,
+            &self.path,
+            &mut self.rows,
+            column,
+            shared.len() - 1,
+        );
+    }
+
+    fn add_candidates(&mut self, node: &Node) {
+        let length = self.path.len() + 1;
+        let ScoreResult { score, score_index } = self.scorer.get_score(&self.rows, length);
+        if score < self.threshold {
+            return;
+        }
+
+        let (start, match_length) = self.scorer.get_match(&self.rows, score_index, length);
+
+        for candidate in &node.candidates {
+            let result = TrieMatch::new(candidate, score, start, match_length, self.term.len());
+            keep_best(&mut self.results, result);
+        }
+    }
+
+    // The cheapest any deeper key can do is to match the rest of the term
+    // against the graphemes still below this node, deleting whatever doesn't
+    // fit. If even that is over budget, nothing below can reach the threshold.
+    fn prune(&self, node: &Node, column: usize, best: f64) -> bool {
+        let term_length = self.term.len();
+        let max_distance =
+            self.scorer
+                .max_distance(self.threshold, term_length, column + node.depth);
+
+        if self.scorer.use_sellers && best <= max_distance {
+            return false;
+        }
+
+        let mut bound = self.bound(column, node.depth);
+        // a transposition can step over the current column entirely
+        if self.scorer.use_damerau && column > 0 {
+            bound = bound.min(self.bound(column - 1, node.depth + 1));
+        }
+
+        bound > max_distance
+    }
+
+    fn bound(&self, column: usize, depth: usize) -> f64 {
+        let term_length = self.term.len();
+        let delete = self.scorer.costs.delete;
+
+        (0..=term_length)
+            .map(|i| {
+                let remaining = term_length.saturating_sub(i + depth);
+                self.rows[i][column] + delete * remaining as f64
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Keeps whichever of an item's key matches sorts first.
+pub(crate) fn keep_best(results: &mut HashMap<usize, TrieMatch>, result: TrieMatch) {
+    match results.get(&result.index) {
+        Some(existing) if compare_matches(existing, &result) != Ordering::Greater => {}
+        _ => {
+            results.insert(result.index, result);
+        }
+    }
+}
+
+/// Orders matches by each criterion of `tie_breaks` in turn, then by
+/// insertion order.
+pub(crate) fn compare_by(a: &TrieMatch, b: &TrieMatch, tie_breaks: &[TieBreak]) -> Ordering {
+    tie_breaks
+        .iter()
+        .fold(Ordering::Equal, |ordering, tie_break| {
+            ordering.then_with(|| match tie_break {
+                TieBreak::Score => b.score.total_cmp(&a.score),
+                TieBreak::MatchIndex => a.match_index.cmp(&b.match_index),
+                TieBreak::KeyIndex => a.key_index.cmp(&b.key_index),
+                TieBreak::KeyLength => a.length_diff.cmp(&b.length_diff),
+                TieBreak::InsertOrder => a.index.cmp(&b.index),
+            })
+        })
+        .then(a.index.cmp(&b.index))
+}
+
+pub(crate) fn compare_matches(a: &TrieMatch, b: &TrieMatch) -> Ordering {
+    compare_by(a, b, &TieBreak::DEFAULT)
 }
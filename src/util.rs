@@ -1,75 +1,57 @@
-use crate::FuzzyOptions;
-use lazy_static::lazy_static;
-use regex::Regex;
-use unicode_normalization::UnicodeNormalization;
+use crate::{transliterate::romanize, FuzzyOptions, Pipeline};
 use unicode_segmentation::UnicodeSegmentation;
 
-lazy_static! {
-    static ref WHITESPACE_REGEX: Regex = Regex::new(r"s+").unwrap();
-    static ref NON_WORD_REGEX: Regex =
-        Regex::new(r#"^[`~!@#$%^&*()\-=_+{}[\]\|\\;':",./<>?]+$"#).unwrap();
-}
-
-#[allow(dead_code)]
+#[derive(Clone, Debug)]
 pub struct Normalized {
-    original: String,
-    normal: Vec<String>,
-    map: Vec<usize>,
+    pub(crate) original: String,
+    pub(crate) normal: Vec<String>,
+    pub(crate) map: Vec<usize>,
 }
 
-pub fn normalize<T>(string: String, options: &FuzzyOptions<T>) -> Normalized {
-    let str_len = string.len();
-
-    let lower = if options.ignore_case {
-        string.to_lowercase()
-    } else {
-        string.clone()
-    };
-
-    let mut normal = vec![];
-    let mut map = vec![];
-    let mut last_whitespace = true;
-    let mut length = 0;
-
-    let grapheme_list = if options.use_separated_unicode {
-        lower
-            .nfkd()
-            .collect::<String>()
-            .split("")
-            .map(ToString::to_string)
-            .collect::<Vec<_>>()
-    } else {
-        lower.graphemes(false).map(ToString::to_string).collect()
-    };
+impl Normalized {
+    pub(crate) fn key(&self) -> String {
+        self.normal.concat()
+    }
 
-    for grapheme in grapheme_list {
-        length += grapheme.len();
+    /// Maps a `start..start + length` grapheme range of `normal` back to a
+    /// byte range of `original`.
+    pub(crate) fn denormalize(&self, start: usize, length: usize) -> (usize, usize) {
+        if self.normal.is_empty() || length == 0 {
+            return (self.map.first().copied().unwrap_or(0), 0);
+        }
 
-        if options.normalize_whitespace && WHITESPACE_REGEX.is_match(&grapheme) {
-            if !last_whitespace {
-                normal.push(" ".into());
-                map.push(length);
-                last_whitespace = true;
-            }
-        } else if !(options.ignore_symbols && NON_WORD_REGEX.is_match(&grapheme)) {
-            normal.push(grapheme);
-            map.push(length);
-            last_whitespace = false;
+        // a range ending inside an expanded grapheme covers all of it
+        let mut end = start + length;
+        while end + 1 < self.map.len() && self.map[end] == self.map[end - 1] {
+            end += 1;
         }
+
+        let index = self.map[start];
+        (index, self.map[end] - index)
     }
+}
 
-    map.push(str_len);
+pub fn normalize<T, U>(string: String, options: &FuzzyOptions<T, U>) -> Normalized {
+    let segments = match &options.pipeline {
+        Some(pipeline) => pipeline.run(&string),
+        None => Pipeline::from_options(options).run(&string),
+    };
 
-    while {
-        if let Some(last) = normal.last() {
-            last == " "
-        } else {
-            false
-        }
-    } {
-        normal.pop();
-        map.pop();
-    }
+    // the match of the last segment ends where the grapheme it came from does,
+    // leaving out what was dropped after it
+    let end = segments.last().map_or(string.len(), |last| {
+        string
+            .grapheme_indices(true)
+            .map(|(index, _)| index)
+            .find(|&index| index > last.offset)
+            .unwrap_or(string.len())
+    });
+
+    let (normal, mut map): (Vec<_>, Vec<_>) = segments
+        .into_iter()
+        .map(|segment| (segment.text, segment.offset))
+        .unzip();
+    map.push(end);
 
     Normalized {
         original: string,
@@ -77,3 +59,30 @@ pub fn normalize<T>(string: String, options: &FuzzyOptions<T>) -> Normalized {
         map,
     }
 }
+
+/// Normalizes a key along with, when `transliterate` is set, its romanized
+/// forms. Every form maps back to the key itself.
+pub(crate) fn normalize_key<T, U>(key: String, options: &FuzzyOptions<T, U>) -> Vec<Normalized> {
+    let romanized = if options.transliterate {
+        romanize(&key)
+    } else {
+        vec![]
+    };
+
+    let mut keys = vec![normalize(key, options)];
+    for form in romanized {
+        let mut normalized = normalize(form.text, options);
+        normalized.map = normalized
+            .map
+            .iter()
+            .map(|&index| form.offsets[index])
+            .collect();
+        normalized.original = keys[0].original.clone();
+
+        if keys.iter().all(|key| key.normal != normalized.normal) {
+            keys.push(normalized);
+        }
+    }
+
+    keys
+}
@@ -0,0 +1,164 @@
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Arc, RwLock, RwLockReadGuard,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{FileSearchError, FileSearcher, MatchData, SearchResult};
+
+// however busy the directory is, changes are applied at least this often,
+// in multiples of the debounce delay
+const MAX_DELAY_FACTOR: u32 = 10;
+
+/// Keeps a [`FileSearcher`] in sync with its root directory, using the
+/// platform's file watching (inotify on Linux).
+///
+/// Events are collected until the directory has been quiet for the debounce
+/// delay, then every touched path is refreshed in one go.
+pub struct FileWatcher {
+    searcher: Arc<RwLock<FileSearcher>>,
+    watcher: Option<RecommendedWatcher>,
+    worker: Option<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct Batch {
+    paths: HashSet<PathBuf>,
+    rescan: bool,
+}
+
+impl Batch {
+    fn add(&mut self, event: notify::Result<Event>) {
+        match event {
+            Ok(event) if !event.need_rescan() => self.paths.extend(event.paths),
+            // events were lost, so only a full rescan can be trusted
+            _ => self.rescan = true,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.paths.is_empty() && !self.rescan
+    }
+
+    fn apply(self, searcher: &RwLock<FileSearcher>) {
+        let mut searcher = searcher.write().unwrap_or_else(|error| error.into_inner());
+
+        // ignore files change what every other path means
+        let rescan = self.rescan
+            || self.paths.iter().any(|path| {
+                path.file_name()
+                    .is_some_and(|name| name == ".gitignore" || name == ".ignore")
+            });
+
+        let result = if rescan {
+            searcher.rescan()
+        } else {
+            searcher.refresh(self.paths)
+        };
+
+        // the path may have gone again before it was walked, so retry from
+        // scratch rather than leave the index half updated
+        if result.is_err() {
+            let _ = searcher.rescan();
+        }
+    }
+}
+
+impl FileWatcher {
+    pub fn new(searcher: FileSearcher, debounce: Duration) -> Result<Self, FileSearchError> {
+        let root = searcher.root().to_path_buf();
+        let searcher = Arc::new(RwLock::new(searcher));
+
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+        })?;
+        watcher.watch(&root, RecursiveMode::Recursive)?;
+
+        let worker = {
+            let searcher = searcher.clone();
+            thread::spawn(move || debounce_events(receiver, &searcher, debounce))
+        };
+
+        // anything created between the scan and the watch would be missed
+        searcher
+            .write()
+            .unwrap_or_else(|error| error.into_inner())
+            .rescan()?;
+
+        Ok(Self {
+            searcher,
+            watcher: Some(watcher),
+            worker: Some(worker),
+        })
+    }
+
+    pub fn searcher(&self) -> RwLockReadGuard<'_, FileSearcher> {
+        self.searcher
+            .read()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.searcher().generation()
+    }
+
+    pub fn search(&self, term: impl ToString) -> Result<Vec<PathBuf>, SearchResult> {
+        self.searcher().search(term)
+    }
+
+    pub fn search_data(
+        &self,
+        term: impl ToString,
+    ) -> Result<Vec<MatchData<PathBuf>>, SearchResult> {
+        self.searcher().search_data(term)
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        // dropping the watcher closes the channel, which stops the worker
+        self.watcher.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn debounce_events(
+    receiver: Receiver<notify::Result<Event>>,
+    searcher: &RwLock<FileSearcher>,
+    debounce: Duration,
+) {
+    let max_delay = debounce * MAX_DELAY_FACTOR;
+
+    while let Ok(event) = receiver.recv() {
+        let started = Instant::now();
+        let mut batch = Batch::default();
+        batch.add(event);
+
+        let disconnected = loop {
+            let wait = debounce.min(max_delay.saturating_sub(started.elapsed()));
+            match receiver.recv_timeout(wait) {
+                Ok(event) => batch.add(event),
+                Err(RecvTimeoutError::Timeout) => break false,
+                Err(RecvTimeoutError::Disconnected) => break true,
+            }
+        };
+
+        if !batch.is_empty() {
+            batch.apply(searcher);
+        }
+
+        if disconnected {
+            break;
+        }
+    }
+}